@@ -1,4 +1,9 @@
-use crate::{Error, options::Derive};
+use crate::{
+	Error,
+	options::{
+		Derive, HashAttribute, LenOptions, ProptestAttribute, SchemarsAttribute, SerdeAttribute,
+	},
+};
 use syn::{
 	Token,
 	parse::{Parse, ParseStream},
@@ -38,9 +43,37 @@ pub enum Attribute {
 	Owned(Punctuated<OwnedTypeAttribute, Token![,]>),
 	Eq(Punctuated<syn::Type, Token![,]>),
 	Ord(Punctuated<syn::Type, Token![,]>),
-	Serde,
+	Serde(Punctuated<SerdeAttribute, Token![,]>),
+	Os,
+	Diesel,
+	Borsh,
+	SerdeJson(proc_macro2::Span),
+	Schemars(Punctuated<SchemarsAttribute, Token![,]>),
+	Proptest(proc_macro2::Span, Punctuated<ProptestAttribute, Token![,]>),
 	NoDeref,
+	Deref(syn::Path),
+	Derive(proc_macro2::Span, Punctuated<Derive, Token![,]>),
 	Infallible,
+	CharTable,
+	Normalize,
+	Trim,
+	Ascii(proc_macro2::Span),
+	CaseInsensitive,
+	Concat(proc_macro2::Span),
+	Collections(proc_macro2::Span),
+	Secret,
+	GenTests,
+	List(proc_macro2::Span, Punctuated<ListTypeAttribute, Token![,]>),
+	Hash(Option<Punctuated<HashAttribute, Token![,]>>),
+	ExactLen(syn::LitInt),
+	Len(LenOptions),
+	NoValidate,
+	ValidateResult(syn::Type),
+	Rc(proc_macro2::Span),
+	Staging(syn::Ident),
+	Empty,
+	ErrorDefault(proc_macro2::Span),
+	SubtypeOf(syn::Path, Option<syn::Path>),
 }
 
 impl Parse for Attribute {
@@ -51,6 +84,18 @@ impl Parse for Attribute {
 			return Ok(Self::NoDeref);
 		}
 
+		if ident == "deref" {
+			let content;
+			syn::parenthesized!(content in input);
+			return content.parse().map(Self::Deref);
+		}
+
+		if ident == "derive" {
+			let content;
+			syn::parenthesized!(content in input);
+			return Punctuated::parse_terminated(&content).map(|d| Self::Derive(ident.span(), d));
+		}
+
 		if ident == "infallible" {
 			return Ok(Self::Infallible);
 		}
@@ -61,9 +106,13 @@ impl Parse for Attribute {
 		}
 
 		if ident == "owned" {
-			let content;
-			syn::parenthesized!(content in input);
-			return Punctuated::parse_terminated(&content).map(Self::Owned);
+			if input.peek(syn::token::Paren) {
+				let content;
+				syn::parenthesized!(content in input);
+				return Punctuated::parse_terminated(&content).map(Self::Owned);
+			}
+
+			return Ok(Self::Owned(Punctuated::new()));
 		}
 
 		if ident == "eq" {
@@ -79,7 +128,154 @@ impl Parse for Attribute {
 		}
 
 		if ident == "serde" {
-			return Ok(Self::Serde);
+			if input.peek(syn::token::Paren) {
+				let content;
+				syn::parenthesized!(content in input);
+				return Punctuated::parse_terminated(&content).map(Self::Serde);
+			}
+
+			return Ok(Self::Serde(Punctuated::new()));
+		}
+
+		if ident == "borsh" {
+			return Ok(Self::Borsh);
+		}
+
+		if ident == "diesel" {
+			return Ok(Self::Diesel);
+		}
+
+		if ident == "serde_json" {
+			return Ok(Self::SerdeJson(ident.span()));
+		}
+
+		if ident == "proptest" {
+			if input.peek(syn::token::Paren) {
+				let content;
+				syn::parenthesized!(content in input);
+				return Punctuated::parse_terminated(&content)
+					.map(|p| Self::Proptest(ident.span(), p));
+			}
+
+			return Ok(Self::Proptest(ident.span(), Punctuated::new()));
+		}
+
+		if ident == "schemars" {
+			if input.peek(syn::token::Paren) {
+				let content;
+				syn::parenthesized!(content in input);
+				return Punctuated::parse_terminated(&content).map(Self::Schemars);
+			}
+
+			return Ok(Self::Schemars(Punctuated::new()));
+		}
+
+		if ident == "os" {
+			return Ok(Self::Os);
+		}
+
+		if ident == "char_table" {
+			return Ok(Self::CharTable);
+		}
+
+		if ident == "normalize" {
+			return Ok(Self::Normalize);
+		}
+
+		if ident == "gen_tests" {
+			return Ok(Self::GenTests);
+		}
+
+		if ident == "list" {
+			let content;
+			syn::parenthesized!(content in input);
+			return Punctuated::parse_terminated(&content).map(|l| Self::List(ident.span(), l));
+		}
+
+		if ident == "hash" {
+			if input.peek(syn::token::Paren) {
+				let content;
+				syn::parenthesized!(content in input);
+				return Punctuated::parse_terminated(&content).map(|h| Self::Hash(Some(h)));
+			}
+
+			return Ok(Self::Hash(None));
+		}
+
+		if ident == "len" {
+			let _: Token![=] = input.parse()?;
+			return input.parse().map(Self::Len);
+		}
+
+		if ident == "exact_len" {
+			let _: Token![=] = input.parse()?;
+			return input.parse().map(Self::ExactLen);
+		}
+
+		if ident == "secret" {
+			return Ok(Self::Secret);
+		}
+
+		if ident == "ascii" {
+			return Ok(Self::Ascii(ident.span()));
+		}
+
+		if ident == "collections" {
+			return Ok(Self::Collections(ident.span()));
+		}
+
+		if ident == "concat" {
+			return Ok(Self::Concat(ident.span()));
+		}
+
+		if ident == "case_insensitive" {
+			return Ok(Self::CaseInsensitive);
+		}
+
+		if ident == "trim" {
+			return Ok(Self::Trim);
+		}
+
+		if ident == "no_validate" {
+			return Ok(Self::NoValidate);
+		}
+
+		if ident == "empty" {
+			return Ok(Self::Empty);
+		}
+
+		if ident == "error_default" {
+			return Ok(Self::ErrorDefault(ident.span()));
+		}
+
+		if ident == "subtype_of" {
+			let content;
+			syn::parenthesized!(content in input);
+			let ty = content.parse()?;
+			let owned = if content.is_empty() {
+				None
+			} else {
+				let _: Token![,] = content.parse()?;
+				Some(content.parse()?)
+			};
+
+			return Ok(Self::SubtypeOf(ty, owned));
+		}
+
+		if ident == "staging" {
+			let content;
+			syn::parenthesized!(content in input);
+			return content.parse().map(Self::Staging);
+		}
+
+		if ident == "rc" {
+			return Ok(Self::Rc(ident.span()));
+		}
+
+		if ident == "validate_result" {
+			let content;
+			syn::parenthesized!(content in input);
+			return content.parse().map(Self::ValidateResult);
 		}
 
 		Err(syn::parse::Error::new(ident.span(), "unknown attribute"))
@@ -89,10 +285,17 @@ impl Parse for Attribute {
 pub enum OwnedTypeAttribute {
 	Ident(syn::Ident),
 	Derive(Punctuated<Derive, Token![,]>),
+	AsRefName(syn::Ident),
+	Cow(syn::Ident),
+	Suffix(syn::LitStr),
 }
 
 impl Parse for OwnedTypeAttribute {
 	fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+		if !input.peek(syn::Ident) {
+			return Err(input.error("expected an owned type name, e.g. `owned(FooString)`"));
+		}
+
 		let ident: syn::Ident = input.parse()?;
 
 		if ident == "derive" {
@@ -101,6 +304,41 @@ impl Parse for OwnedTypeAttribute {
 			return Punctuated::parse_terminated(&content).map(Self::Derive);
 		}
 
+		if ident == "as_ref_name" {
+			let content;
+			syn::parenthesized!(content in input);
+			return content.parse().map(Self::AsRefName);
+		}
+
+		if ident == "cow" {
+			let content;
+			syn::parenthesized!(content in input);
+			return content.parse().map(Self::Cow);
+		}
+
+		if ident == "suffix" {
+			let _: Token![=] = input.parse()?;
+			return input.parse().map(Self::Suffix);
+		}
+
+		Ok(Self::Ident(ident))
+	}
+}
+
+pub enum ListTypeAttribute {
+	Ident(syn::Ident),
+	Separator(syn::LitStr),
+}
+
+impl Parse for ListTypeAttribute {
+	fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+		let ident: syn::Ident = input.parse()?;
+
+		if ident == "sep" {
+			let _: Token![=] = input.parse()?;
+			return input.parse().map(Self::Separator);
+		}
+
 		Ok(Self::Ident(ident))
 	}
 }
@@ -136,3 +374,107 @@ impl Parse for Derive {
 		Err(syn::parse::Error::new(ident.span(), "unsupported trait"))
 	}
 }
+
+impl Parse for SerdeAttribute {
+	fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+		let ident: syn::Ident = input.parse()?;
+
+		if ident == "human_readable" {
+			return Ok(Self::HumanReadable);
+		}
+
+		Err(syn::parse::Error::new(ident.span(), "unknown serde option"))
+	}
+}
+
+impl Parse for SchemarsAttribute {
+	fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+		let ident: syn::Ident = input.parse()?;
+
+		if ident == "format" {
+			let _: Token![=] = input.parse()?;
+			return input.parse().map(Self::Format);
+		}
+
+		if ident == "pattern" {
+			let _: Token![=] = input.parse()?;
+			return input.parse().map(Self::Pattern);
+		}
+
+		Err(syn::parse::Error::new(
+			ident.span(),
+			"unknown schemars option",
+		))
+	}
+}
+
+impl Parse for ProptestAttribute {
+	fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+		let ident: syn::Ident = input.parse()?;
+
+		if ident == "regex" {
+			let _: Token![=] = input.parse()?;
+			return input.parse().map(Self::Regex);
+		}
+
+		Err(syn::parse::Error::new(
+			ident.span(),
+			"unknown proptest option",
+		))
+	}
+}
+
+impl Parse for LenOptions {
+	/// Parses either an integer or a range of integers (`a..b`, `a..=b`, `a..`,
+	/// `..b` or `..=b`).
+	fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+		let span = input.span();
+		let start: Option<syn::LitInt> = input.parse()?;
+		let min = start.as_ref().map(syn::LitInt::base10_parse).transpose()?;
+
+		let max = if input.peek(Token![..=]) {
+			let _: Token![..=] = input.parse()?;
+			let end: syn::LitInt = input.parse()?;
+			Some(end.base10_parse()?)
+		} else if input.peek(Token![..]) {
+			let _: Token![..] = input.parse()?;
+			let end: Option<syn::LitInt> = input.parse()?;
+			match end {
+				Some(end) => {
+					let end: usize = end.base10_parse()?;
+					Some(
+						end.checked_sub(1)
+							.ok_or_else(|| syn::parse::Error::new(span, "invalid length range"))?,
+					)
+				}
+				None => None,
+			}
+		} else {
+			match min {
+				Some(n) => Some(n),
+				None => return Err(input.error("expected an integer or a range of integers")),
+			}
+		};
+
+		let min = min.unwrap_or(0);
+
+		// Empty ranges reject everything, and `..` accepts everything.
+		if max.map_or(min == 0, |max| max < min) {
+			return Err(syn::parse::Error::new(span, "invalid length range"));
+		}
+
+		Ok(Self { span, min, max })
+	}
+}
+
+impl Parse for HashAttribute {
+	fn parse(input: ParseStream) -> syn::parse::Result<Self> {
+		let ident: syn::Ident = input.parse()?;
+
+		if ident == "fxhash" {
+			return Ok(Self::FxHash);
+		}
+
+		Err(syn::parse::Error::new(ident.span(), "unsupported hasher"))
+	}
+}
@@ -18,13 +18,32 @@ mod utils;
 /// ```ignore
 /// #[derive(StrNewType)]
 /// #[newtype(...)]
+/// #[repr(transparent)]
 /// pub struct Type(str);
 /// ```
 ///
+/// The `#[repr(transparent)]` attribute is required since the generated
+/// constructors convert `&str` references into `&Type` references.
+///
 /// The target type must implement two `const` compatible validation methods:
 /// - `validate_bytes(&[u8]) -> bool`; and
 /// - `validate_str(&str) -> bool`.
 ///
+/// Byte inputs are checked to be valid UTF-8 before being passed to
+/// `validate_bytes`, which hence does not need to check it.
+/// These methods are not required with the `no_validate` sub-attribute, and
+/// return `Result<(), E>` instead of `bool` with the `validate_result`
+/// sub-attribute.
+///
+/// The generated code only depends on `core`, and on `alloc` for items
+/// involving allocations (owned types, `Cow` and `Box` conversions, etc.)
+/// which are only compiled with the `alloc` feature of `str-newtype`. The
+/// generated code refers to the `alloc` crate through `str-newtype`, so the
+/// deriving crate does not need to declare `extern crate alloc`, and the
+/// features are checked in the deriving crate (not in this macro, whose
+/// features Cargo would unify across the whole build).
+/// Conversions to OS strings (`os` sub-attribute) require the `std` feature.
+///
 /// The macro will then derive various methods, types and trait implementations
 /// depending on the sub-attributes specified in the `newtype` attribute.
 ///
@@ -34,72 +53,179 @@ mod utils;
 /// passed to the `newtype` attribute.
 ///
 /// - If the `infallible` sub-attribute is *not* set:
-///   - Error type `Invalid{Type}<T = String>(pub T);` with
+///   - Error type `Invalid{Type}<T = String>(pub T);` (without the `String`
+///     default if the `alloc` feature is disabled), or
+///     `Invalid{Type}<T = String, E = Reason>(pub T, pub Option<E>)` with the
+///     `validate_result(Reason)` sub-attribute, with
 ///     - `Debug` implementation
 ///     - `Display` implementation
 ///     - `Error` implementation
+///     - `into_inner(self) -> T` and `const as_inner(&self) -> &T` methods
+///       returning the invalid input
+///     - `map<U>(self, f: impl FnOnce(T) -> U) -> Invalid{Type}<U>` method,
+///       preserving the failure reason, e.g. to turn an error on a `&str`
+///       into an error on a `String`
+///     - `Clone`, `PartialEq`, `Eq` and `Hash` implementations, when
+///       implemented by `T` (and the reason type)
+///     - `suggestion(&self) -> Option<String>` method, if the `normalize`
+///       sub-attribute is set
 ///   - `Type::new<T: ?Sized + AsRef<[u8]>>(input: &T) -> Result<&Self, Invalid{Type}<&T>>` constructor;
 ///   - `const Type::from_bytes(input: &[u8]) -> Result<&Self, Invalid{Type}<&[u8]>>` constructor;
 ///   - `const Type::from_str(input: &str) -> Result<&Str, Invalid{Type}<&str>>` constructor;
+///   - `const Type::from_static(input: &'static str) -> &'static Self`
+///     constructor, panicking on invalid inputs (a compile-time error in
+///     `const` contexts);
+///   - `const Type::is_valid(input: &str) -> bool`, performing the same checks
+///     as the constructors (including `len`);
 ///   - `&Type: TryFrom<&str>`
+///   - `Type::with_char<R>(c: char, f: impl FnOnce(Result<&Self, Invalid{Type}<&str>>) -> R) -> R`
+///     calling `f` on the single character value (without allocation)
 /// - If the `infallible` sub-attribute is set:
 ///   - `Type::new<T: ?Sized + AsRef<[u8]>>(input: &T) -> &Self` constructor;
 ///   - `const Type::from_bytes(input: &[u8]) -> &Self` constructor;
 ///   - `const Type::from_str(input: &str) -> &Self` constructor;
 ///   - `&Type: From<&str>`
+///   - `Type::with_char<R>(c: char, f: impl FnOnce(&Self) -> R) -> R`
 /// - `&Type: TryFrom<&[u8]>`
+/// - `const Type::as_str(&self) -> &str`
+/// - `const Type::as_bytes(&self) -> &[u8]`
+/// - `const Type::as_ptr(&self) -> *const u8`
+/// - `const Type::len(&self) -> usize`
+/// - `const Type::is_empty(&self) -> bool`
+/// - `const Type::const_eq(&self, other: &str) -> bool`
 /// - `Type: AsRef<Self>`
 /// - `Type: AsRef<str>`
 /// - `Type: AsRef<[u8]>`
+/// - `Box<Type>: AsRef<str>` and `Box<Type>: AsRef<[u8]>` (`Rc<Type>` and
+///   `Arc<Type>` cannot implement them because of the orphan rules)
 /// - `Type: Display`
 /// - `Type: Debug`
 /// - `Type: Borrow<str>`
 /// - `&str: From<&Type>`
 /// - `&[u8]: From<&Type>`
-/// - If the `noderef` sub-attribute is *not* set:
-///   - `Type: Deref<str>`
+/// - `Cow<str>: From<&Type>` (borrowed)
+/// - `Type: PartialEq<str>`, `Type: PartialEq<&str>` and
+///   `Type: PartialEq<String>` (comparing bytes), and the symmetric
+///   implementations, as well as `&Type: PartialEq<String>` (and symmetric)
+/// - If the `no_deref` sub-attribute is *not* set:
+///   - `Type: Deref<str>`, or `Type: Deref<Target = Target>` with the
+///     `deref(Target)` sub-attribute
 /// - If the `eq(Other)` attribute is set:
-///   - `Type: PartialEq<Other>`
+///   - `Type: PartialEq<Other>` (comparing bytes, without validating `Other`)
 ///   - `Other: PartialEq<Type>`
 /// - If the `ord(Other)` attribute is set:
-///   - `Type: PartialOrd<Other>`
+///   - `Type: PartialOrd<Other>` (comparing bytes, without validating `Other`)
 ///   - `Other: PartialOrd<Type>`
 /// - If the `serde` attribute is set:
 ///   - `Type: ::serde::Serialize`
 ///   - `&Type: ::serde::Deserialize<'_>`
+///   - `Box<Type>: ::serde::Deserialize<'_>` (which, with serde's `rc`
+///     feature, also covers `Rc<Type>` and `Arc<Type>`)
+/// - If the `os` attribute is set:
+///   - `Type: AsRef<OsStr>`
+///   - `&Type: TryFrom<&CStr>` (failing on non UTF-8 or invalid C strings)
+/// - If the `serde_json` attribute is set:
+///   - `&Type: TryFrom<&serde_json::Value>` (borrowing the JSON string)
+/// - If the `char_table` attribute is set:
+///   - `const Type::from_char(c: char) -> Option<&'static Type>`
+///   - `const Type::ALL: &'static [&'static Type]` listing every valid single
+///     character value
+///   - `Type::range(from: &Type, to: &Type) -> impl Iterator<Item = &'static Type>`
+///     iterating over the values of `Type::ALL` between `from` and `to`
+/// - If the `ascii` sub-attribute is set:
+///   - `Type::eq_ignore_ascii_case(&self, other: &str) -> bool`
+///   - `Type::try_to_ascii_lowercase(&self) -> Result<OwnedType, Invalid{Type}>`
+///     and `Type::try_to_ascii_uppercase(&self) -> Result<OwnedType, Invalid{Type}>`,
+///     if the `owned` sub-attribute is set
+/// - If the `case_insensitive` sub-attribute is set:
+///   - `Type::eq_ignore_case(&self, other: &Self) -> bool`
+///   - `Type::case_insensitive(&self) -> CaseInsensitive<&Type>`
+/// - If the `derive(...)` sub-attribute is set, each listed trait among
+///   `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash` on `Type`
+/// - If the `hash(fxhash)` attribute is set:
+///   - `Type::fx_hash(&self) -> u64` (requires the `fxhash` feature of
+///     `str-newtype`)
 /// - If the `owned(OwnedType, ...)` sub-attribute is set (where `...` denotes
 ///   the owned-type sub-attributes):
 ///   - If the `infallible` sub-attribute is *not* set:
-///     - `struct OwnedType(String)`
+///     - `#[repr(transparent)] struct OwnedType(String)`
 ///     - `OwnedType::new<T: str_newtype::Buffer>(input: T) -> Result<Self, Invalid{Type}<T>>`
 ///     - `OwnedType::from_bytes(input: Vec<u8>) -> Result<Self, Invalid{Type}<Vec<u8>>>`
 ///     - `OwnedType::from_string(input: String) -> Result<Self, Invalid{Type}>`
-///     - `unsafe OwnedType::new_unchecked(input: impl Into<Vec<u8>>) -> Self`
+///     - `OwnedType::from_cow(input: Cow<str>) -> Result<Self, Invalid{Type}<Cow<str>>>`
+///       (validating before copying borrowed inputs, and reusing owned ones,
+///       through the `Buffer` implementation of `Cow<str>`)
+///     - `OwnedType::try_join(iter: impl IntoIterator<Item = Self>, sep: &str) -> Result<Self, Invalid{Type}>`
+///       (validating the joined result)
+///     - `OwnedType::try_replace(&mut self, from: &str, to: &str) -> Result<(), Invalid{Type}>`
+///       (leaving `self` unchanged if the result is invalid)
+///     - `OwnedType::try_mutate(&mut self, f: impl FnOnce(&mut String)) -> Result<(), Invalid{Type}>`
+///       (leaving `self` unchanged if the result is invalid)
+///     - `OwnedType::try_push_str(&mut self, s: &str) -> Result<(), Invalid{Type}>`
+///       and `OwnedType::try_push(&mut self, c: char) -> Result<(), Invalid{Type}>`
+///       (truncating back to the original length if the result is invalid)
+///     - `OwnedType::append_all(&mut self, iter: impl IntoIterator<Item: AsRef<str>>) -> Result<(), Invalid{Type}>`
+///       (validating once, after every item has been appended)
 ///     - `OwnedType: TryFrom<String>`
+///     - `OwnedType: TryFrom<Box<str>>` (reusing the allocation)
+///     - `OwnedType: TryFrom<Cow<str>>` (reusing the allocation of owned
+///       values, and validating borrowed values before copying them)
+///     - `OwnedType: TryFrom<&str>` (validating before allocating)
 ///   - If the `infallible` sub-attribute is set:
-///     - `struct OwnedType(pub String)`
+///     - `#[repr(transparent)] struct OwnedType(pub String)`
 ///     - `OwnedType::new(input: impl Into<String>) -> Self`
 ///     - `OwnedType::from_string(input: String) -> Self`
+///     - `OwnedType::from_cow(input: Cow<str>) -> Self`
+///     - `OwnedType::mutate(&mut self, f: impl FnOnce(&mut String))`
+///     - `OwnedType::push_str(&mut self, s: &str)` and `OwnedType::push(&mut self, c: char)`
+///     - `OwnedType: Extend<&str> + Extend<String> + Extend<char>`
 ///     - `OwnedType::from_bytes(input: Vec<u8>) -> Result<Self, ::std::string::FromUtf8Error>`
 ///     - `OwnedType: From<String>`
+///     - `OwnedType: From<Box<str>>` (reusing the allocation)
+///     - `OwnedType: From<Cow<str>>`
+///     - `OwnedType: From<&str>`
+///   - `unsafe OwnedType::new_unchecked(input: impl Into<Vec<u8>>) -> Self`
 ///   - `OwnedType: Display`
 ///   - `OwnedType: Debug`
-///   - `OwnedType: Clone`
-///   - `OwnedType: FromStr`
+///   - `OwnedType: Clone` (inlined, with `clone_from` reusing the existing
+///     allocation)
+///   - `OwnedType: FromStr`, whose error (`Invalid{Type}` or `Infallible`)
+///     converts into `Box<dyn Error + Send + Sync>` (and carries the failure
+///     reason with `validate_result`)
 ///   - `Type: ToOwned<Owned => OwnedType>`
+///   - `OwnedType: From<&Type>`, allocating exactly the needed capacity
+///   - `&Type: From<&OwnedType>` (hence `TryFrom<&OwnedType>` with
+///     `Infallible` as error, for generic code)
 ///   - `OwnedType: Deref<Target = Type>`
 ///   - `OwnedType: TryFrom<Vec<u8>>`
-///   - `OwnedType::as_{type}(&self) -> &Type` where `{type}` is the camel case version of `Type`.
+///   - `OwnedType: TryFrom<[u8; N]>`
+///   - `OwnedType: TryFrom<&Vec<u8>>` (validating before allocating)
+///   - `OwnedType: TryFrom<Cow<[u8]>>` (reusing the allocation of owned
+///     bytes)
+///   - `OwnedType::as_{type}(&self) -> &Type` where `{type}` is the snake case
+///     version of `Type` (the name can be changed with the `as_ref_name`
+///     owned-type sub-attribute).
 ///   - `OwnedType::as_str(&self) -> &str`
 ///   - `OwnedType::as_bytes(&self) -> &[u8]`
+///   - `OwnedType::to_string_owned(&self) -> String` (cloning the inner string,
+///     cheaper than `to_string`)
 ///   - `OwnedType::into_string(self) -> String`
 ///   - `OwnedType::into_bytes(self) -> Vec<u8>`
 ///   - `OwnedType: Borrow<Type>`
 ///   - `OwnedType: AsRef<Type>`
 ///   - `OwnedType: AsRef<str>`
 ///   - `OwnedType: AsRef<[u8]>`
-///   - `String: From<OwnedType>`
+///   - `Type::into_cow(&self) -> Cow<Type>` (borrowed) and
+///     `OwnedType::into_cow(self) -> Cow<'static, Type>` (owned)
+///   - `Cow<Type>: From<&Type>` and `Cow<Type>: From<OwnedType>`
+///   - `OwnedType::into_boxed(self) -> Box<Type>` and
+///     `Type::into_owned(self: Box<Self>) -> OwnedType`, reusing the allocation
+///   - `OwnedType: From<Box<Type>>` and `Box<Type>: From<OwnedType>`
+///   - `String: From<OwnedType>` (moving the inner string, without copy)
 ///   - `Vec<u8>: From<OwnedType>`
+///   - `Arc<str>: From<OwnedType>`
+///   - `Rc<str>: From<OwnedType>`
+///   - `Vec<String>: Extend<OwnedType> + FromIterator<OwnedType>`
 ///   - If the `eq(Other)` attribute is set:
 ///     - `OwnedType: PartialEq<Other>`
 ///     - `Other: PartialEq<OwnedType>`
@@ -109,18 +235,89 @@ mod utils;
 ///   - If the `serde` attribute is set:
 ///     - `OwnedType: ::serde::Serialize`
 ///     - `OwnedType: ::serde::Deserialize<'_>`
+///     - `Type::deserialize_cow<'a, 'de: 'a, D>(deserializer: D) -> Result<Cow<'a, Type>, D::Error>`,
+///       borrowing from the input when possible (to be used with
+///       `#[serde(borrow, deserialize_with = "Type::deserialize_cow")]`)
+///   - If the `os` attribute is set:
+///     - `OwnedType: AsRef<OsStr>`
+///     - `OsString: From<OwnedType>`
+///     - `OwnedType: TryFrom<&OsStr>` and `OwnedType: TryFrom<OsString>`
+///       (failing on non UTF-8 or invalid values, returning the input)
+///   - If the `serde_json` attribute is set:
+///     - `OwnedType: TryFrom<serde_json::Value>`
 ///   - If the `derive(Default)` owned-type sub-attribute is set:
 ///     - `OwnedType: Default` (requires `Type: Default`)
 ///   - If the `derive(PartialEq)` owned-type sub-attribute is set:
 ///     - `OwnedType: PartialEq` (requires `Type: PartialEq`)
+///     - `PartialEq` between each pair of `OwnedType`, `Box<Type>`,
+///       `Arc<Type>` and `&Type`
+///     - `OwnedType: PartialEq<str>`, `OwnedType: PartialEq<&str>` and
+///       `OwnedType: PartialEq<String>`, and the symmetric implementations
 ///   - If the `derive(Eq)` owned-type sub-attribute is set:
 ///     - `OwnedType: Eq` (requires `Type: Eq`)
 ///   - If the `derive(PartialOrd)` owned-type sub-attribute is set:
 ///     - `OwnedType: PartialOrd` (requires `Type: PartialOrd`)
+///     - `PartialOrd` between each pair of `OwnedType`, `Box<Type>`,
+///       `Arc<Type>` and `&Type`
 ///   - If the `derive(Ord)` owned-type sub-attribute is set:
 ///     - `OwnedType: Ord` (requires `Type: Ord`)
+///   - If the `collections` sub-attribute is set:
+///     - `type OwnedTypeMap<V, S = RandomState> = HashMap<OwnedType, V, S>`
+///     - `type OwnedTypeSet<S = RandomState> = HashSet<OwnedType, S>`
+///   - If the `concat` sub-attribute is set:
+///     - `OwnedType: Add<&Type, Output = OwnedType>`
+///     - `OwnedType: AddAssign<&Type>`
 ///   - If the `derive(Hash)` owned-type sub-attribute is set:
-///     - `OwnedType: Hash` (requires `Type: Hash`)
+///     - `OwnedType: Hash`, hashing the value as a `str` (just like `Type`
+///       with the `hash` sub-attribute)
+///
+/// # Byte new-types
+///
+/// The macro can also be used on `[u8]` new-types (`pub struct Type([u8]);`),
+/// in which case only `validate_bytes` is required and inputs are not checked
+/// to be valid UTF-8. The UTF-8 specific items (`as_str`, `Display`,
+/// `FromStr`, `str` and `String` conversions, etc.) are omitted, and the
+/// owned type wraps a `Vec<u8>`. The derived items are:
+/// - Error type `Invalid{Type}<T = Vec<u8>>(pub T)` (or
+///   `Invalid{Type}<T = Vec<u8>, E = Reason>(pub T, pub Option<E>)`), whose
+///   `Display` implementation formats the input using `Debug` (with the same
+///   `into_inner`, `as_inner` and `map` methods, and conditional `Clone`,
+///   `PartialEq`, `Eq` and `Hash` implementations)
+/// - `Type::new`, `const Type::from_bytes` and `const Type::from_static`
+///   constructors, taking bytes, and `const Type::is_valid(&[u8]) -> bool`
+///   (only `new` and `from_bytes` when `infallible` is set)
+/// - `&Type: TryFrom<&[u8]>` (`From` when `infallible` is set)
+/// - `const Type::new_unchecked(&[u8]) -> &Self`
+/// - `const Type::as_bytes`, `const Type::len` and `const Type::is_empty`
+/// - `Type: Deref<Target = [u8]>` (unless `no_deref` is set), `AsRef<[u8]>`,
+///   `AsRef<Type>` and `Borrow<[u8]>`
+/// - `Debug`, `LowerHex` and `UpperHex` implementations (`{:#x}` adds the
+///   `0x` prefix, and the width, fill and `0` flag are respected like for
+///   integers, e.g. `{:08x}`)
+/// - `Type: Index<I>` for any `I: SliceIndex<[u8]>`, returning a `u8` for
+///   `usize` indices and a `[u8]` slice for ranges (even with `no_deref`)
+/// - `PartialEq` with `[u8]`, `&[u8]` and `Vec<u8>`
+/// - `const Type::validate_len` with `len` or `exact_len`
+/// - `Type: Hash`, hashing the value as a `[u8]`, with `hash`
+/// - With `owned(OwnedType)`:
+///   - `OwnedType::new(Vec<u8>) -> Result<Self, Invalid{Type}>` (or
+///     `new(impl Into<Vec<u8>>) -> Self` when `infallible` is set)
+///   - `unsafe OwnedType::new_unchecked(Vec<u8>) -> Self`, when fallible
+///   - `OwnedType::as_{type}`, `OwnedType::as_bytes` and
+///     `OwnedType::into_bytes`
+///   - `OwnedType: TryFrom<Vec<u8>>`, `TryFrom<&[u8]>` and
+///     `TryFrom<[u8; N]>` (`From<Vec<u8>>` and `From<&[u8]>` when
+///     `infallible` is set)
+///   - `Type: ToOwned<Owned = OwnedType>`, `OwnedType: From<&Type>`,
+///     `&Type: From<&OwnedType>` and `Vec<u8>: From<OwnedType>`
+///   - `OwnedType: Clone`, `Deref<Target = Type>`, `Borrow<Type>`,
+///     `AsRef<Type>`, `AsRef<[u8]>`, `Debug`, `LowerHex` and `UpperHex`
+///   - The `Default`, `PartialEq`, `Eq`, `PartialOrd`, `Ord` and `Hash` owned
+///     type derives, delegating to `Type`
+///
+/// The `name`, `owned`, `no_deref`, `infallible`, `hash`, `len`, `exact_len`,
+/// `no_validate` and `validate_result` sub-attributes are supported. Other
+/// sub-attributes, which only make sense for strings, are rejected.
 ///
 /// # The `newtype` attribute
 ///
@@ -129,25 +326,255 @@ mod utils;
 ///
 /// ```ignore
 /// #[derive(StrNewType)]
-/// #[newtype(eq(str, [u8]), ord(str), noderef, owned(Foo, derive(Default, Hash)))]
+/// #[newtype(eq([u8]), ord(str), no_deref, owned(Foo, derive(Default, Hash)))]
+/// #[repr(transparent)]
 /// pub struct Type(str);
 /// ```
 ///
 /// Here is the list of sub-attributes:
-/// - `noderef`: Prevent the `Type: Deref<Target = str>` implementation.
+/// - `no_deref`: Prevent the `Type: Deref<Target = str>` implementation.
+/// - `deref(Target)`: Dereference to `Target`, another `str` new-type, instead
+///   of `str`, so that the methods of the weaker type are available on `Type`
+///   (and `str` remains reachable transitively). Just like `subtype_of`, this
+///   is a zero-cost pointer cast, and it is the responsibility of the user to
+///   ensure that every valid `Type` is a valid `Target`. The `AsRef<str>` and
+///   `Borrow<str>` implementations are unaffected. Cannot be used with
+///   `no_deref`.
 /// - `eq`: Implement `Type: PartialEq<Other>` (and
 ///   `OwnedType: PartialEq<Other>` if applicable) where `Other` must appear in
 ///   a parenthesized comma-separated list after the sub-attribute
-///   (`eq(A, B, C)`).
+///   (`eq(A, B, C)`). Comparisons with `str`, `&str` and `String` are always
+///   implemented and do not need to be listed.
 /// - `ord`: Implement `Type: PartialOrd<Other>` (and
 ///   `OwnedType: PartialOrd<Other>` if applicable) where `Other` must appear in
 ///   a parenthesized comma-separated list after the sub-attribute
-///   (`ord(A, B, C)`).
+///   (`ord(A, B, C)`). This also implements `PartialEq<Other>`, hence `Other`
+///   must not be repeated in the `eq` sub-attribute.
 /// - `serde`: Implement `Type: Serialize + Deserialize` (and
-///   `OwnedType: Serialize + Deserialize` if applicable)
+///   `OwnedType: Serialize + Deserialize` if applicable). Can take a
+///   parenthesized comma-separated list of options:
+///   - `human_readable`: Serialize as a string only for human-readable
+///     formats (such as JSON), and as bytes otherwise (such as bincode).
+///     Deserialization mirrors this choice.
+/// - `schemars`: Implement `Type: JsonSchema` (and `OwnedType: JsonSchema`
+///   if applicable, sharing the same schema) from `schemars` 1.x, describing
+///   the value as a string. Can take a parenthesized comma-separated list of
+///   options adding grammar hints to the schema:
+///   - `format = "..."`: Set the `format` of the schema (e.g. `"iri"`).
+///   - `pattern = "..."`: Set the `pattern` regular expression of the schema.
+/// - `borsh`: Implement `Type: BorshSerialize` (and
+///   `OwnedType: BorshSerialize + BorshDeserialize` if applicable),
+///   serialized just like a `String`. Deserialization validates the value,
+///   failing with an `InvalidData` I/O error if it is invalid. Requires the
+///   `borsh` feature of `str-newtype`.
+/// - `diesel`: Implement `Type: ToSql<Text, DB>` for any `diesel` backend
+///   `DB` (and `OwnedType: ToSql<Text, DB> + FromSql<Text, DB> + Queryable`
+///   if applicable, validating the value on read), along with the
+///   `AsExpression` conversions to `Text` and `Nullable<Text>` expressions,
+///   so the types can be used in queries and as fields of `Queryable` and
+///   `Insertable` structures.
+/// - `serde_json`: Implement conversions from JSON values (`serde_json::Value`),
+///   failing on non-string or invalid values. Cannot be used with
+///   `infallible`.
+/// - `os`: Implement conversions to OS strings (`OsStr` and `OsString`), and
+///   from C strings (`CStr`).
+/// - `rc`: Derive `Type::to_arc(&self) -> Arc<Type>` and
+///   `Type::to_rc(&self) -> Rc<Type>`, along with `Arc<Type>: From<&Type>` and
+///   `Rc<Type>: From<&Type>` (and from `OwnedType` if applicable). Just like
+///   `Arc<str>`, the value is copied into a single new allocation holding the
+///   reference counts (the buffer of an `OwnedType` cannot be reused).
+///   With the `serde` sub-attribute, also derive
+///   `Type::deserialize_arc` and `Type::deserialize_rc`, validating the
+///   deserialized value (to be used with
+///   `#[serde(deserialize_with = "Type::deserialize_arc")]`, since
+///   `Arc<Type>: Deserialize` can only be implemented by `serde` itself, which
+///   it does through `Box<Type>` with its `rc` feature).
+///   Requires the `alloc` feature.
+/// - `char_table`: Derive the `Type::from_char` constructor returning a
+///   `&'static Type` for single-character values, without allocation. Only
+///   ASCII characters are covered. The `Type::ALL` constant then lists every
+///   such valid value.
+/// - `normalize`: The type must provide a `normalize(&str) -> String` method
+///   returning the canonical form of its input. Owned constructors store the
+///   canonical form of their input, while the `validate_*` methods are
+///   expected to only accept canonical forms. Borrowed constructors hence
+///   reject non-canonical inputs, and the returned error can suggest the
+///   canonical form through `Invalid{Type}::suggestion`.
+/// - `secret`: The `Display` and `Debug` implementations of `Type` (and
+///   `OwnedType`, `ListType` items and the `staging` builder if applicable)
+///   print a fixed `[REDACTED Type]` placeholder instead of the value, and
+///   `Invalid{Type}` omits the rejected input, so that the value cannot be
+///   leaked by accident (e.g. in logs). The value remains available through
+///   `as_str`, `into_string`, etc.
+/// - `trim`: Owned constructors (`OwnedType::new` and every conversion
+///   based on it, such as `FromStr`) remove the leading and trailing
+///   whitespace of their input before validating it (and normalizing it with
+///   `normalize`). The input buffer is reused when possible. Borrowed
+///   constructors are not affected, since they cannot trim in place. Neither
+///   are the mutation methods of the owned type (`push_str`, `push`,
+///   `mutate`, `Extend`, `try_push_str`, `try_mutate`, `try_replace`, etc.),
+///   which validate (and normalize) the resulting buffer as is.
+/// - `ascii`: Only accept ASCII inputs, in addition to the `validate_*`
+///   methods, and derive `Type::eq_ignore_ascii_case(&self, &str) -> bool`.
+///   With an owned type, also derive
+///   `Type::try_to_ascii_lowercase(&self) -> Result<OwnedType, Invalid{Type}>` and
+///   `Type::try_to_ascii_uppercase(&self) -> Result<OwnedType, Invalid{Type}>`,
+///   which validate their result since changing the case may not preserve
+///   the invariant of the type. Cannot be used with `infallible`.
+/// - `collections`: Derive the `OwnedTypeMap<V, S = RandomState>` and
+///   `OwnedTypeSet<S = RandomState>` aliases of `HashMap` and `HashSet` keyed
+///   by `OwnedType` (whose `derive(Eq, Hash)` owned-type sub-attribute makes
+///   them usable). Requires the `owned` sub-attribute and the `std` feature.
+/// - `concat`: Implement `OwnedType: Add<&Type>` and
+///   `OwnedType: AddAssign<&Type>`, appending the right-hand side to the
+///   buffer of the left-hand side without validating the result.
+///
+///   **Safety:** this asserts that the type is closed under concatenation,
+///   i.e. that appending a valid `Type` to a valid `Type` always gives a valid
+///   `Type` (in canonical form, with `normalize`), which is not checked. It
+///   is the responsibility of the user to ensure it.
+///   Requires the `owned` sub-attribute.
+/// - `case_insensitive`: Derive `Type::eq_ignore_case` and
+///   `Type::case_insensitive`, wrapping the value into a
+///   `str_newtype::CaseInsensitive` whose `PartialEq`, `Ord` and `Hash`
+///   implementations agree on the Unicode lowercase mapping of the value, so
+///   that it can be used as a case-insensitive map key.
+/// - `len = N` or `len = Range`: Only accept inputs whose byte length is `N`,
+///   or in the given range of integers (`a..b`, `a..=b`, `a..`, `..b` or
+///   `..=b`, e.g. `len = 1..=64`), in addition to the `validate_*` methods.
+///   This derives the `const Type::validate_len(&[u8]) -> bool` method
+///   performing the check. Combined with `no_validate`, the length is the
+///   only constraint (on top of UTF-8 validity) and no validation method needs
+///   to be written. Cannot be used with `infallible`.
+/// - `exact_len = N`: Same as `len = N`.
+/// - `no_validate`: The type does not provide the `validate_*` methods. Only
+///   the built-in checks (UTF-8 validity, `ascii` and `len`) are performed.
+/// - `validate_result(Reason)`: The `validate_*` methods return
+///   `Result<(), Reason>` instead of `bool`, and the error explains why the
+///   input is invalid. The reason is stored in the second field of
+///   `Invalid{Type}` (and printed by its `Display` implementation), which is
+///   `None` if a built-in check failed (UTF-8 validity, `ascii`, `len`,
+///   etc.).
+///   Since validation is performed in `const` constructors, `Reason` should not
+///   implement `Drop`. Cannot be used with `infallible` or `no_validate`.
+///   The reason is preserved by every constructor, including `FromStr`, so it
+///   can for instance carry the byte offset of the first invalid character,
+///   letting callers map parse errors to a line and column:
+///   ```ignore
+///   #[derive(StrNewType)]
+///   #[newtype(validate_result(usize), owned(DigitString))]
+///   #[repr(transparent)]
+///   pub struct DigitStr(str);
+///
+///   impl DigitStr {
+///     pub const fn validate_bytes(s: &[u8]) -> Result<(), usize> {
+///       let mut i = 0;
+///       while i < s.len() {
+///         if !s[i].is_ascii_digit() {
+///           return Err(i);
+///         }
+///         i += 1;
+///       }
+///       Ok(())
+///     }
+///
+///     pub const fn validate_str(s: &str) -> Result<(), usize> {
+///       Self::validate_bytes(s.as_bytes())
+///     }
+///   }
+///
+///   let error = "12a4".parse::<DigitString>().unwrap_err();
+///   assert_eq!(error.1, Some(2));
+///   ```
+/// - `error_default`: Implement `Invalid{Type}<T>: Default` when
+///   `T: Default`, for frameworks requiring error types to have a default
+///   value. The reason (with `validate_result`) defaults to `None`. Cannot be
+///   used with `infallible`.
+/// - `hash`: Implement `Type: Hash`, hashing the value as a `str` (consistently
+///   with `Type: Borrow<str>`). The owned type `derive(Hash)` also hashes as a
+///   `str`, so both types hash the same. Hashing can be further configured
+///   with a parenthesized comma-separated list of options (which alone does
+///   not implement `Hash`, e.g. `hash(fxhash)`):
+///   - `fxhash`: Derive `Type::fx_hash`, computing a fast non DoS-resistant
+///     hash of the value, meant for trusted internal caches. Requires the
+///     `fxhash` feature of `str-newtype`.
+/// - `derive`: Implement the given traits on `Type`, delegating to the
+///   underlying `str`, given as a parenthesized comma-separated list (e.g.
+///   `derive(PartialEq, Eq, Hash)`). This mirrors the owned-type `derive`
+///   sub-attribute, whose implementations delegate to the ones of `Type`.
+///   Possible traits are:
+///   - `PartialEq`
+///   - `Eq`, which requires `Type: PartialEq`
+///   - `PartialOrd`, which requires `Type: PartialEq`
+///   - `Ord`, which requires `Type: Eq + PartialOrd`
+///   - `Hash`, equivalent to the `hash` sub-attribute
+///
+///   The required traits can either be derived at the same time or
+///   implemented separately. `Default` cannot be derived on an unsized type:
+///   use the `empty` sub-attribute to implement `&Type: Default` instead.
+/// - `empty`: Assert that the empty string is a valid value, and derive the
+///   `Type::EMPTY` constant along with `&Type: Default`. The assertion is
+///   checked at compile time, failing with an error naming the type if the
+///   empty string is rejected. The owned type then provides the
+///   non-allocating `const OwnedType::new_empty() -> Self` constructor and
+///   implements `Default` (the `derive(Default)` owned-type sub-attribute is
+///   not required).
+/// - `gen_tests`: Generate `#[cfg(test)]` compile-time assertions that `Type`,
+///   `OwnedType` and `Invalid{Type}` (when applicable) are
+///   `Send + Sync + Unpin`.
+/// - `list(ListType, sep = ",")`: Derive a `ListType(pub Vec<OwnedType>)`
+///   list of values, parsed (with `FromStr`) by splitting the input on the
+///   given separator (`","` by default) and validating each item. The empty
+///   string is parsed as an empty list. The list also implements `Display`
+///   (joining the items with the separator), `Debug`,
+///   `Deref<Target = [OwnedType]>` and `IntoIterator`, as well as
+///   `Serialize + Deserialize` (as a separated string) with the `serde`
+///   sub-attribute. The `PartialEq`, `Eq` and `Hash` traits are derived on
+///   the list when they are derived on `OwnedType` (with `ListType::dedup`
+///   removing consecutive repeated items), so it can be used as a map key.
+///   Requires the `owned` sub-attribute.
+/// - `staging(Builder)`: Derive a `Builder(pub String)` staging type holding
+///   a string under construction, without any invariant. It dereferences
+///   mutably to `String` and implements `fmt::Write`, so it can be freely
+///   edited, and is validated once by
+///   `Builder::build(self) -> Result<OwnedType, Invalid{Type}>` (returning
+///   `OwnedType` directly if the type is `infallible`). Requires the `owned`
+///   sub-attribute.
+/// - `subtype_of(WideType)` or `subtype_of(WideType, WideOwnedType)`:
+///   Declare that every `Type` is a valid `WideType`, another `str` new-type
+///   (e.g. every absolute IRI is an IRI). This implements
+///   `&WideType: From<&Type>` and `Type: AsRef<WideType>` through a zero-cost
+///   pointer cast, without re-validation, along with
+///   `OwnedType: AsRef<WideType>` if applicable. If the owned type of
+///   `WideType` is given, `WideOwnedType: From<OwnedType>` is also
+///   implemented through `WideOwnedType::new_unchecked`, reusing the buffer
+///   (which requires the `owned` sub-attribute, and fails to compile if
+///   `WideOwnedType` is not the owned type of an `str` new-type). Can be
+///   repeated for multiple wider types.
+///
+///   **Safety:** the implication between the two invariants is not checked.
+///   It is the responsibility of the user to ensure that every valid `Type`
+///   is a valid `WideType`, and that both types are derived with
+///   `StrNewType`.
+/// - `proptest`: Derive `OwnedType::arb() -> impl Strategy<Value = OwnedType>`
+///   generating valid values for property testing, by filtering arbitrary
+///   strings (which is only practical for permissive grammars). Can take a
+///   parenthesized comma-separated list of options:
+///   - `regex = "..."`: Generate the candidate strings from the given regular
+///     expression instead, which is much more efficient when it matches the
+///     grammar. Candidates are still validated.
+///
+///   Requires the `owned` sub-attribute and the `proptest` feature of
+///   `str-newtype`.
 /// - `owned(OwnedType)`: Derive an owned variant of `Type` called `OwnedType`.
+///   The identifier can be omitted (e.g. `owned` or `owned(derive(Hash))`), in
+///   which case the trailing `Str` of the type name is replaced with `String`,
+///   or `Buf` is appended if the name does not end with `Str` (`IriStr` gives
+///   `IriString` and `Iri` gives `IriBuf`).
 ///   This sub-attribute can take additional owned-type sub-attributes after the
 ///   identifier:
+///   - `suffix`: Specifies the suffix of the default owned type name (e.g.
+///     `suffix = "Buf"` turns `IriStr` into `IriBuf`).
 ///   - `derive`: Specifies the list of trait to derive on `OwnedType`. Must be
 ///   given as a parenthesized comma-separated list (e.g.
 ///   `derive(Default, Hash)`). Possible traits are:
@@ -157,6 +584,10 @@ mod utils;
 ///     - `PartialOrd`
 ///     - `Ord`
 ///     - `Hash`
+///   - `as_ref_name`: Specifies the name of the `OwnedType::as_{type}` method
+///     (e.g. `as_ref_name(as_foo)`).
+///   - `cow`: Derive a `Cow<Type>` type alias with the given name (e.g.
+///     `cow(FooCow)` derives `type FooCow<'a> = Cow<'a, Type>`).
 #[proc_macro_derive(StrNewType, attributes(newtype))]
 #[proc_macro_error]
 pub fn derive_regular_grammar(input_tokens: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -187,12 +618,60 @@ enum Error {
 	#[error("unexpected field")]
 	UnexpectedField(Span),
 
-	#[error("expected `str` type")]
+	#[error("expected `str` or `[u8]` type")]
 	ExpectedStr(Span),
 
+	#[error("expected `#[repr(transparent)]` type")]
+	MissingReprTransparent(Span),
+
 	#[error("invalid attribute")]
 	InvalidAttribute(Span),
 
+	#[error("missing list type identifier")]
+	MissingListIdent(Span),
+
+	#[error("the `list` attribute requires an owned type")]
+	ListWithoutOwned(Span),
+
+	#[error("the `staging` attribute requires an owned type")]
+	StagingWithoutOwned(Span),
+
+	#[error("the `proptest` attribute requires an owned type")]
+	ProptestWithoutOwned(Span),
+
+	#[error("the `concat` attribute requires an owned type")]
+	ConcatWithoutOwned(Span),
+
+	#[error("the `collections` attribute requires an owned type")]
+	CollectionsWithoutOwned(Span),
+
+	#[error("the owned `subtype_of` type requires an owned type")]
+	SubtypeWithoutOwned(Span),
+
+	#[error("the `serde_json` attribute requires a fallible type")]
+	InfallibleSerdeJson(Span),
+
+	#[error("the `ascii` attribute requires a fallible type")]
+	InfallibleAscii(Span),
+
+	#[error("the `len` attribute requires a fallible type")]
+	InfallibleLen(Span),
+
+	#[error("the `validate_result` attribute cannot be used with `infallible` or `no_validate`")]
+	ConflictingValidateResult(Span),
+
+	#[error("`Default` cannot be derived on the borrowed type (see the `empty` attribute)")]
+	BorrowedDefault(Span),
+
+	#[error("the `deref` attribute cannot be used with `no_deref`")]
+	ConflictingDeref(Span),
+
+	#[error("the `error_default` attribute requires a fallible type")]
+	InfallibleErrorDefault(Span),
+
+	#[error("the `{1}` attribute is not supported on `[u8]` new-types")]
+	UnsupportedBytesAttribute(Span, &'static str),
+
 	#[error(transparent)]
 	Syn(#[from] syn::Error),
 }
@@ -206,7 +685,23 @@ impl Error {
 			Self::UnexpectedNamedFields(s) => *s,
 			Self::UnexpectedField(s) => *s,
 			Self::ExpectedStr(s) => *s,
+			Self::MissingReprTransparent(s) => *s,
 			Self::InvalidAttribute(s) => *s,
+			Self::MissingListIdent(s) => *s,
+			Self::ListWithoutOwned(s) => *s,
+			Self::StagingWithoutOwned(s) => *s,
+			Self::ProptestWithoutOwned(s) => *s,
+			Self::ConcatWithoutOwned(s) => *s,
+			Self::CollectionsWithoutOwned(s) => *s,
+			Self::SubtypeWithoutOwned(s) => *s,
+			Self::InfallibleSerdeJson(s) => *s,
+			Self::InfallibleAscii(s) => *s,
+			Self::InfallibleLen(s) => *s,
+			Self::ConflictingValidateResult(s) => *s,
+			Self::BorrowedDefault(s) => *s,
+			Self::ConflictingDeref(s) => *s,
+			Self::InfallibleErrorDefault(s) => *s,
+			Self::UnsupportedBytesAttribute(s, _) => *s,
 			Self::Syn(e) => e.span(),
 		}
 	}
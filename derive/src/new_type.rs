@@ -5,10 +5,18 @@ use syn::spanned::Spanned;
 use crate::{
 	Error,
 	attribute::extract_attributes,
-	options::{Derive, ForeignOptions, Options, OwnedTypeOptions},
-	utils::SnakeCase,
+	options::{
+		Derive, Derives, ForeignOptions, LenOptions, ListTypeOptions, Options, OwnedTypeOptions,
+		SerdeOptions,
+	},
+	utils::{
+		SnakeCase, alloc_path, cfg_alloc, cfg_alloc_else, cfg_std, require_alloc, require_std,
+		std_path,
+	},
 };
 
+mod bytes;
+
 pub fn derive(input: syn::DeriveInput) -> Result<TokenStream, Error> {
 	match input.data {
 		syn::Data::Struct(s) => match s.fields {
@@ -16,25 +24,102 @@ pub fn derive(input: syn::DeriveInput) -> Result<TokenStream, Error> {
 				let mut fields = unnamed.unnamed.into_iter();
 
 				let field = fields.next().unwrap();
-				match &field.ty {
-					syn::Type::Path(p) if p.path.is_ident("str") => (),
+				let bytes = match &field.ty {
+					syn::Type::Path(p) if p.path.is_ident("str") => false,
+					syn::Type::Slice(s) if matches!(&*s.elem, syn::Type::Path(p) if p.path.is_ident("u8")) => {
+						true
+					}
 					_ => return Err(Error::ExpectedStr(field.ty.span())),
-				}
+				};
 
 				if let Some(u) = fields.next() {
 					return Err(Error::UnexpectedField(u.span()));
 				}
 
+				if !is_repr_transparent(&input.attrs) {
+					return Err(Error::MissingReprTransparent(input.ident.span()));
+				}
+
 				let mut options = Options::default();
 
 				extract_attributes(&input.attrs, |attrs| {
 					for attr in attrs.0 {
-						options.apply(attr)?;
+						options.apply(&input.ident, attr)?;
 					}
 
 					Ok(())
 				})?;
 
+				if let Some(span) = options.foreign.serde_json.filter(|_| options.infallible) {
+					return Err(Error::InfallibleSerdeJson(span));
+				}
+
+				if let Some(span) = options.ascii.filter(|_| options.infallible) {
+					return Err(Error::InfallibleAscii(span));
+				}
+
+				if let Some(len) = options.len.as_ref().filter(|_| options.infallible) {
+					return Err(Error::InfallibleLen(len.span));
+				}
+
+				if let Some(ty) = options
+					.validate_result
+					.as_ref()
+					.filter(|_| options.infallible || options.no_validate)
+				{
+					return Err(Error::ConflictingValidateResult(ty.span()));
+				}
+
+				if let Some(target) = options.deref.as_ref().filter(|_| options.no_deref) {
+					return Err(Error::ConflictingDeref(target.span()));
+				}
+
+				if let Some(span) = options.error_default.filter(|_| options.infallible) {
+					return Err(Error::InfallibleErrorDefault(span));
+				}
+
+				if let Some(list) = options.list.as_ref().filter(|_| options.owned.is_none()) {
+					return Err(Error::ListWithoutOwned(list.ident.span()));
+				}
+
+				if let Some(staging) = options.staging.as_ref().filter(|_| options.owned.is_none())
+				{
+					return Err(Error::StagingWithoutOwned(staging.span()));
+				}
+
+				if let Some(proptest) = options
+					.proptest
+					.as_ref()
+					.filter(|_| options.owned.is_none())
+				{
+					return Err(Error::ProptestWithoutOwned(proptest.span));
+				}
+
+				if let Some(span) = options.collections.filter(|_| options.owned.is_none()) {
+					return Err(Error::CollectionsWithoutOwned(span));
+				}
+
+				if let Some(span) = options.concat.filter(|_| options.owned.is_none()) {
+					return Err(Error::ConcatWithoutOwned(span));
+				}
+
+				if let Some(owned) = options
+					.subtypes
+					.iter()
+					.find_map(|s| s.owned.as_ref())
+					.filter(|_| options.owned.is_none())
+				{
+					return Err(Error::SubtypeWithoutOwned(owned.span()));
+				}
+
+				if bytes {
+					if let Some(attr) = bytes::unsupported_option(&options) {
+						return Err(Error::UnsupportedBytesAttribute(field.ty.span(), attr));
+					}
+
+					return Ok(bytes::derive_with_options(input.ident, &options));
+				}
+
 				Ok(derive_with_options(input.ident, &options))
 			}
 			syn::Fields::Unit => Err(Error::UnexpectedUnitStruct(input.ident.span())),
@@ -45,16 +130,52 @@ pub fn derive(input: syn::DeriveInput) -> Result<TokenStream, Error> {
 	}
 }
 
+/// Checks that the type is `#[repr(transparent)]`, which the generated
+/// constructors rely on to transmute `&str` into `&Type`.
+fn is_repr_transparent(attrs: &[syn::Attribute]) -> bool {
+	attrs
+		.iter()
+		.filter(|attr| attr.path().is_ident("repr"))
+		.any(|attr| {
+			let mut transparent = false;
+			attr.parse_nested_meta(|meta| {
+				transparent |= meta.path.is_ident("transparent");
+				Ok(())
+			})
+			.is_ok() && transparent
+		})
+}
+
 fn derive_with_options(ident: syn::Ident, options: &Options) -> TokenStream {
+	let alloc = alloc_path();
 	let error = (!options.infallible).then(|| format_ident!("Invalid{ident}"));
 
 	let debug_name = ident.to_string();
 	let name = options.name(&ident);
 
+	let fmt_value = if options.secret {
+		let placeholder = format!("[REDACTED {ident}]");
+		quote!(f.write_str(#placeholder))
+	} else {
+		quote!(f.write_str(self.as_str()))
+	};
+
 	let new_method_link = format!("[`{ident}::new`]");
 
-	let deref = (!options.no_deref).then(|| {
-		quote! {
+	let deref = (!options.no_deref).then(|| match &options.deref {
+		Some(target) => quote! {
+			impl ::core::ops::Deref for #ident {
+				type Target = #target;
+
+				fn deref(&self) -> &#target {
+					// Both types are `#[repr(transparent)]` wrappers around
+					// `str`, and the `deref` attribute asserts that every value
+					// of this type is a valid value of the target type.
+					unsafe { &*(self as *const Self as *const #target) }
+				}
+			}
+		},
+		None => quote! {
 			impl ::core::ops::Deref for #ident {
 				type Target = str;
 
@@ -62,673 +183,3085 @@ fn derive_with_options(ident: syn::Ident, options: &Options) -> TokenStream {
 					&self.0
 				}
 			}
-		}
+		},
 	});
 
 	let owned_type = options
 		.owned
 		.as_ref()
-		.map(|owned| derive_owned_type(&name, &ident, owned, &options.foreign, error.as_ref()));
+		.map(|owned| derive_owned_type(&name, &ident, options, owned, error.as_ref()))
+		.map(|owned_type| {
+			require_alloc(
+				owned_type,
+				"owned types require the `alloc` feature of `str-newtype`",
+			)
+		});
+
+	let list_type = options
+		.list
+		.as_ref()
+		.zip(options.owned.as_ref())
+		.map(|(list, owned)| derive_list_type(&name, list, owned, options.foreign.serde.is_some()))
+		.map(cfg_alloc);
 
-	let eq = options
-		.foreign
-		.eq
-		.iter()
-		.chain(&options.foreign.ord)
-		.map(|ty| partial_eq_impl(&ident, ty, !options.infallible));
+	let staging_type = options
+		.staging
+		.as_ref()
+		.zip(options.owned.as_ref())
+		.map(|(staging, owned)| {
+			derive_staging_type(&name, staging, owned, error.as_ref(), options.secret)
+		})
+		.map(cfg_alloc);
+
+	let eq = builtin_eq_types()
+		.into_iter()
+		.chain(options.foreign.eq_types().cloned())
+		.map(|ty| partial_eq_impl(&ident, &ty));
+
+	let string_ty: syn::Type = syn::parse_quote!(#alloc::string::String);
+	let string_eq = partial_eq_impl(&ident, &string_ty);
+	let string_eq = cfg_alloc(quote! {
+		#string_eq
+
+		impl PartialEq<#alloc::string::String> for &#ident {
+			fn eq(&self, other: &#alloc::string::String) -> bool {
+				self.as_bytes() == other.as_bytes()
+			}
+		}
+
+		impl PartialEq<&#ident> for #alloc::string::String {
+			fn eq(&self, other: &&#ident) -> bool {
+				self.as_bytes() == other.as_bytes()
+			}
+		}
+	});
 
 	let ord = options
 		.foreign
 		.ord
 		.iter()
-		.map(|ty| partial_ord_impl(&ident, ty, !options.infallible));
+		.map(|ty| partial_ord_impl(&ident, ty));
+
+	let serialize = options.foreign.serde.as_ref().map(|serde| {
+		let serialize = serialize_str(serde);
 
-	let serialize = options.foreign.serde.then(|| {
 		quote! {
 			impl ::serde::Serialize for #ident {
 				fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 				where
 					S: ::serde::ser::Serializer
 				{
-					<str as ::serde::Serialize>::serialize(self.as_str(), serializer)
+					#serialize
 				}
 			}
 		}
 	});
 
-	let deserialize = options.foreign.serde.then(|| {
-		if error.is_some() {
-			quote! {
-				impl<'a, 'de> ::serde::Deserialize<'de> for &'a #ident where 'de: 'a {
+	let deserialize = options.foreign.serde.as_ref().map(|serde| {
+		let deserialize_borrowed = deserialize_str(serde, true);
+
+		let boxed = {
+			let deserialize_owned = deserialize_str(serde, false);
+
+			let into_boxed = quote! {
+				let s = s.into_boxed_str();
+				Ok(unsafe { #alloc::boxed::Box::from_raw(#alloc::boxed::Box::into_raw(s) as *mut #ident) })
+			};
+
+			let body = match &error {
+				Some(error) => validate(
+					&ident,
+					options,
+					Input::Str(quote!(&s)),
+					&into_boxed,
+					|reason| {
+						let error = error_value(options, error, quote!(s), reason);
+						quote!(Err(::serde::de::Error::custom(#error)))
+					},
+				),
+				None => into_boxed,
+			};
+
+			cfg_alloc(quote! {
+				impl<'de> ::serde::Deserialize<'de> for #alloc::boxed::Box<#ident> {
 					fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 					where
 						D: ::serde::de::Deserializer<'de>
 					{
-						#ident::from_str(<&'a str as ::serde::Deserialize<'de>>::deserialize(deserializer)?)
-							.map_err(::serde::de::Error::custom)
+						let s: #alloc::string::String = #deserialize_owned;
+						#body
 					}
 				}
+			})
+		};
+
+		let from_str = if error.is_some() {
+			quote! {
+				#ident::from_str(s).map_err(::serde::de::Error::custom)
 			}
 		} else {
 			quote! {
-				impl<'a, 'de> ::serde::Deserialize<'de> for &'a #ident where 'de: 'a {
-					fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-					where
-						D: ::serde::de::Deserializer<'de>
-					{
-						<&'a str as ::serde::Deserialize<'de>>::deserialize(deserializer).map(#ident::from_str)?
-					}
+				Ok(#ident::from_str(s))
+			}
+		};
+
+		quote! {
+			impl<'a, 'de> ::serde::Deserialize<'de> for &'a #ident where 'de: 'a {
+				fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+				where
+					D: ::serde::de::Deserializer<'de>
+				{
+					let s: &'a str = #deserialize_borrowed;
+					#from_str
 				}
 			}
+
+			#boxed
 		}
 	});
 
-	let constructor = match error {
-		Some(error) => {
-			quote! {
-				/// Invalid
-				#[doc = #name]
-				/// error.
-				///
-				/// This error is raised by the
-				#[doc = #new_method_link]
-				/// when the input is not a valid
-				#[doc = concat!(#name, ".")]
-				pub struct #error<T = String>(pub T);
+	let schemars = options.foreign.schemars.as_ref().map(|schemars| {
+		let ident_str = ident.to_string();
+		let format = schemars.format.as_ref().map(|f| quote!("format": #f,));
+		let pattern = schemars.pattern.as_ref().map(|p| quote!("pattern": #p,));
 
-				impl<T: ::core::fmt::Debug> ::core::fmt::Debug for #error<T> {
-					fn fmt(&self, f: &mut core::fmt::Formatter) -> ::core::fmt::Result {
-						f.write_str(#debug_name)?;
-						write!(f, "(")?;
-						self.0.fmt(f)?;
-						write!(f, ")")
-					}
+		let schema = quote! {
+			impl ::schemars::JsonSchema for #ident {
+				fn schema_name() -> #alloc::borrow::Cow<'static, str> {
+					#alloc::borrow::Cow::Borrowed(#ident_str)
 				}
 
-				impl<T: ::core::fmt::Display> ::core::fmt::Display for #error<T> {
-					fn fmt(&self, f: &mut core::fmt::Formatter) -> ::core::fmt::Result {
-						write!(f, "invalid ")?;
-						f.write_str(#name)?;
-						write!(f, ": ")?;
-						self.0.fmt(f)
-					}
+				fn schema_id() -> #alloc::borrow::Cow<'static, str> {
+					#alloc::borrow::Cow::Borrowed(concat!(module_path!(), "::", #ident_str))
 				}
 
-				impl<T: ::core::fmt::Debug + ::core::fmt::Display> ::core::error::Error for #error<T> {}
-
-				impl #ident {
-					/// Creates a new
-					#[doc = #name]
-					/// by parsing the input value.
-					pub fn new<T: ?Sized + AsRef<[u8]>>(input: &T) -> Result<&Self, #error<&T>> {
-						let bytes = input.as_ref();
-						if Self::validate_bytes(bytes) {
-							Ok(unsafe {
-								Self::new_unchecked_from_bytes(bytes)
-							})
-						} else {
-							Err(#error(input))
-						}
-					}
-
-					/// Creates a new
-					#[doc = #name]
-					/// by parsing the input bytes.
-					pub const fn from_bytes(input: &[u8]) -> Result<&Self, #error<&[u8]>> {
-						if Self::validate_bytes(input) {
-							Ok(unsafe {
-								Self::new_unchecked_from_bytes(input)
-							})
-						} else {
-							Err(#error(input))
-						}
-					}
-
-					/// Creates a new
-					#[doc = #name]
-					/// by parsing the input string.
-					pub const fn from_str(input: &str) -> Result<&Self, #error<&str>> {
-						if Self::validate_str(input) {
-							Ok(unsafe {
-								Self::new_unchecked(input)
-							})
-						} else {
-							Err(#error(input))
-						}
-					}
+				fn json_schema(_: &mut ::schemars::SchemaGenerator) -> ::schemars::Schema {
+					::schemars::json_schema!({
+						"type": "string",
+						#format
+						#pattern
+					})
+				}
+			}
+		};
 
-					/// Creates a new
-					#[doc = #name]
-					/// from the input bytes without validation.
-					///
-					/// # Safety
-					/// The input bytes must be a valid
-					#[doc = concat!(#name, ".")]
-					pub const unsafe fn new_unchecked_from_bytes(input: &[u8]) -> &Self {
-						unsafe { std::mem::transmute::<&[u8], &Self>(input) }
-					}
+		require_alloc(
+			schema,
+			"the `schemars` attribute requires the `alloc` feature of `str-newtype`",
+		)
+	});
 
-					/// Creates a new
-					#[doc = #name]
-					/// from the input string without validation.
-					///
-					/// # Safety
-					/// The input string must be a valid
-					#[doc = concat!(#name, ".")]
-					pub const unsafe fn new_unchecked(input: &str) -> &Self {
-						unsafe { Self::new_unchecked_from_bytes(input.as_bytes()) }
-					}
+	let borsh = options.foreign.borsh.then(|| {
+		quote! {
+			impl str_newtype::borsh::BorshSerialize for #ident {
+				fn serialize<W: str_newtype::borsh::io::Write>(&self, writer: &mut W) -> str_newtype::borsh::io::Result<()> {
+					<str as str_newtype::borsh::BorshSerialize>::serialize(self.as_str(), writer)
 				}
+			}
+		}
+	});
 
-				impl<'a> TryFrom<&'a [u8]> for &'a #ident {
-					type Error = #error<&'a [u8]>;
+	let diesel = options.foreign.diesel.then(|| {
+		let as_expression = diesel_as_expression(&quote!(#ident), false);
 
-					fn try_from(value: &'a[u8]) -> Result<&'a #ident, #error<&'a [u8]>> {
-						#ident::new(value)
-					}
+		quote! {
+			impl<DB> ::diesel::serialize::ToSql<::diesel::sql_types::Text, DB> for #ident
+			where
+				DB: ::diesel::backend::Backend,
+				str: ::diesel::serialize::ToSql<::diesel::sql_types::Text, DB>
+			{
+				fn to_sql<'b>(&'b self, out: &mut ::diesel::serialize::Output<'b, '_, DB>) -> ::diesel::serialize::Result {
+					<str as ::diesel::serialize::ToSql<::diesel::sql_types::Text, DB>>::to_sql(self.as_str(), out)
 				}
+			}
 
-				impl<'a> TryFrom<&'a str> for &'a #ident {
-					type Error = #error<&'a str>;
+			#as_expression
+		}
+	});
 
-					fn try_from(value: &'a str) -> Result<&'a #ident, #error<&'a str>> {
-						#ident::new(value)
+	let serde_json = options.foreign.serde_json.and(error.as_ref()).map(|error| {
+		let map_error = map_error(options, error, quote!(value));
+		let invalid = error_value(options, error, quote!(value), quote!(None));
+		quote! {
+			impl<'a> TryFrom<&'a ::serde_json::Value> for &'a #ident {
+				type Error = #error<&'a ::serde_json::Value>;
+
+				fn try_from(value: &'a ::serde_json::Value) -> Result<Self, #error<&'a ::serde_json::Value>> {
+					match value {
+						::serde_json::Value::String(s) => #ident::from_str(s).map_err(#map_error),
+						_ => Err(#invalid)
 					}
 				}
 			}
 		}
-		None => {
-			quote! {
-				impl #ident {
-					/// Creates a new
-					#[doc = #name]
-					/// by parsing the input value.
-					pub fn new<T: ?Sized + AsRef<str>>(input: &T) -> &Self {
-						Self::from_str(input.as_ref())
-					}
+	});
 
-					/// Creates a new
-					#[doc = #name]
-					/// by parsing the input bytes.
-					pub const fn from_bytes(input: &[u8]) -> Result<&Self, ::std::str::Utf8Error> {
-						match ::std::str::from_utf8(input) {
-							Ok(s) => Ok(unsafe { Self::from_str(s) }),
-							Err(e) => Err(e)
+	let os = options.foreign.os.then(|| {
+		let try_from_cstr = match &error {
+			Some(error) => {
+				let map_error = map_error(options, error, quote!(value));
+				let invalid = error_value(options, error, quote!(value), quote!(None));
+				quote! {
+					impl<'a> TryFrom<&'a ::core::ffi::CStr> for &'a #ident {
+						type Error = #error<&'a ::core::ffi::CStr>;
+
+						fn try_from(value: &'a ::core::ffi::CStr) -> Result<Self, #error<&'a ::core::ffi::CStr>> {
+							match value.to_str() {
+								Ok(s) => #ident::from_str(s).map_err(#map_error),
+								Err(_) => Err(#invalid)
+							}
 						}
 					}
-
-					/// Creates a new
-					#[doc = #name]
-					/// by parsing the input string.
-					pub const fn from_str(input: &str) -> &Self {
-						unsafe { std::mem::transmute::<&str, &Self>(input) }
-					}
 				}
+			}
+			None => quote! {
+				impl<'a> TryFrom<&'a ::core::ffi::CStr> for &'a #ident {
+					type Error = ::core::str::Utf8Error;
 
-				impl<'a> TryFrom<&'a [u8]> for &'a #ident {
-					type Error = ::std::str::Utf8Error;
-
-					fn try_from(value: &'a[u8]) -> Result<&'a #ident, ::std::str::Utf8Error> {
-						#ident::from_bytes(value)
+					fn try_from(value: &'a ::core::ffi::CStr) -> Result<Self, ::core::str::Utf8Error> {
+						value.to_str().map(#ident::from_str)
 					}
 				}
-
-				impl<'a> From<&'a str> for &'a #ident {
-					fn from(value: &'a str) -> &'a #ident {
-						#ident::new(value)
-					}
+			},
+		};
+
+		let std = std_path();
+		let os_str = cfg_std(quote! {
+			impl AsRef<#std::ffi::OsStr> for #ident {
+				fn as_ref(&self) -> &#std::ffi::OsStr {
+					self.as_str().as_ref()
 				}
 			}
-		}
-	};
-
-	quote! {
-		#constructor
+		});
 
-		impl #ident {
-			/// Returns the
-			#[doc = #name]
-			/// as a string.
-			pub const fn as_str(&self) -> &str {
-				&self.0
-			}
+		quote! {
+			#os_str
 
-			/// Returns the
-			#[doc = #name]
-			/// as a byte string.
-			pub const fn as_bytes(&self) -> &[u8] {
-				self.0.as_bytes()
-			}
+			#try_from_cstr
 		}
+	});
 
-		impl AsRef<#ident> for #ident {
-			fn as_ref(&self) -> &#ident {
-				self
+	let cow = cfg_alloc(quote! {
+		impl<'a> From<&'a #ident> for #alloc::borrow::Cow<'a, str> {
+			fn from(value: &'a #ident) -> Self {
+				#alloc::borrow::Cow::Borrowed(value.as_str())
 			}
 		}
 
-		impl AsRef<str> for #ident {
+		impl AsRef<str> for #alloc::boxed::Box<#ident> {
 			fn as_ref(&self) -> &str {
 				self.as_str()
 			}
 		}
 
-		impl AsRef<[u8]> for #ident {
+		impl AsRef<[u8]> for #alloc::boxed::Box<#ident> {
 			fn as_ref(&self) -> &[u8] {
-				self.as_str().as_bytes()
+				self.as_bytes()
 			}
 		}
+	});
 
-		impl ::core::fmt::Display for #ident {
-			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-				f.write_str(self.as_str())
-			}
-		}
+	let derives = options
+		.derives
+		.iter()
+		.map(|d| d.generate_borrowed(&ident, &options.derives));
 
-		impl ::core::fmt::Debug for #ident {
-			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-				f.write_str(self.as_str())
-			}
-		}
+	let ascii = options.ascii.map(|_| {
+		let case = options.owned.as_ref().map(|owned| {
+			let owned_ident = &owned.ident;
+			let error = error.as_ref().unwrap();
 
-		impl ::core::borrow::Borrow<str> for #ident {
-			fn borrow(&self) -> &str {
-				self.as_str()
+			cfg_alloc(quote! {
+				/// Returns a copy of this
+				#[doc = #name]
+				/// where each ASCII letter is mapped to its lowercase equivalent.
+				///
+				/// The result is validated again, since changing the case may
+				/// not preserve the invariant of the type.
+				pub fn try_to_ascii_lowercase(&self) -> Result<#owned_ident, #error> {
+					#owned_ident::new(self.as_str().to_ascii_lowercase())
+				}
+
+				/// Returns a copy of this
+				#[doc = #name]
+				/// where each ASCII letter is mapped to its uppercase equivalent.
+				///
+				/// The result is validated again, since changing the case may
+				/// not preserve the invariant of the type.
+				pub fn try_to_ascii_uppercase(&self) -> Result<#owned_ident, #error> {
+					#owned_ident::new(self.as_str().to_ascii_uppercase())
+				}
+			})
+		});
+
+		quote! {
+			impl #ident {
+				/// Checks that two values are an ASCII case-insensitive match.
+				pub fn eq_ignore_ascii_case(&self, other: &str) -> bool {
+					self.as_str().eq_ignore_ascii_case(other)
+				}
+
+				#case
 			}
 		}
+	});
 
-		impl<'a> From<&'a #ident> for &'a str {
-			fn from(value: &'a #ident) -> Self {
-				value.as_str()
+	let case_insensitive = options.case_insensitive.then(|| {
+		quote! {
+			impl #ident {
+				/// Checks that two values are equal, ignoring case.
+				///
+				/// Both values are compared through their Unicode lowercase
+				/// mapping, just like [`str_newtype::CaseInsensitive`].
+				pub fn eq_ignore_case(&self, other: &Self) -> bool {
+					self.case_insensitive() == other.case_insensitive()
+				}
+
+				/// Wraps this
+				#[doc = #name]
+				/// so that it is compared and hashed case-insensitively (e.g. as
+				/// a `HashMap` key).
+				pub fn case_insensitive(&self) -> str_newtype::CaseInsensitive<&Self> {
+					str_newtype::CaseInsensitive(self)
+				}
 			}
 		}
+	});
 
-		impl<'a> From<&'a #ident> for &'a [u8] {
-			fn from(value: &'a #ident) -> Self {
-				value.as_bytes()
+	let subtypes = options.subtypes.iter().map(|subtype| {
+		let wide = &subtype.ty;
+		let owned = options.owned.as_ref().map(|owned| {
+			let owned_ident = &owned.ident;
+			let from_owned = subtype.owned.as_ref().map(|wide_owned| {
+				quote! {
+					impl From<#owned_ident> for #wide_owned {
+						#[inline]
+						fn from(value: #owned_ident) -> Self {
+							// The `subtype_of` attribute asserts that every
+							// value of this type is a valid value of the wider
+							// type. Going through its constructor (instead of
+							// casting) checks that it is an owned `str`
+							// new-type, reusing the buffer.
+							unsafe { <#wide_owned>::new_unchecked(value.into_string()) }
+						}
+					}
+				}
+			});
+
+			cfg_alloc(quote! {
+				impl AsRef<#wide> for #owned_ident {
+					#[inline]
+					fn as_ref(&self) -> &#wide {
+						<&#wide>::from(&**self)
+					}
+				}
+
+				#from_owned
+			})
+		});
+
+		quote! {
+			impl<'a> From<&'a #ident> for &'a #wide {
+				#[inline]
+				fn from(value: &'a #ident) -> Self {
+					// Both types are `#[repr(transparent)]` wrappers around
+					// `str`, and the `subtype_of` attribute asserts that every
+					// value of this type is a valid value of the wider type.
+					unsafe { &*(value as *const #ident as *const #wide) }
+				}
+			}
+
+			impl AsRef<#wide> for #ident {
+				#[inline]
+				fn as_ref(&self) -> &#wide {
+					self.into()
+				}
 			}
+
+			#owned
 		}
+	});
 
-		#deref
+	let rc = options.rc.map(|_| {
+		let from_owned = options.owned.as_ref().map(|owned| {
+			let owned_ident = &owned.ident;
+			quote! {
+				impl From<#owned_ident> for #alloc::sync::Arc<#ident> {
+					fn from(value: #owned_ident) -> Self {
+						let arc: #alloc::sync::Arc<str> = value.into_string().into();
+						unsafe { #alloc::sync::Arc::from_raw(#alloc::sync::Arc::into_raw(arc) as *const #ident) }
+					}
+				}
 
-		#(#eq)*
+				impl From<#owned_ident> for #alloc::rc::Rc<#ident> {
+					fn from(value: #owned_ident) -> Self {
+						let rc: #alloc::rc::Rc<str> = value.into_string().into();
+						unsafe { #alloc::rc::Rc::from_raw(#alloc::rc::Rc::into_raw(rc) as *const #ident) }
+					}
+				}
+			}
+		});
 
-		#(#ord)*
+		let deserialize = options.foreign.serde.is_some().then(|| {
+			quote! {
+				impl #ident {
+					/// Deserializes a validated
+					#[doc = #name]
+					/// into a new atomically reference-counted allocation.
+					///
+					/// This can be used with
+					/// `#[serde(deserialize_with = "Type::deserialize_arc")]`
+					/// without enabling the `rc` feature of `serde`.
+					pub fn deserialize_arc<'de, D>(deserializer: D) -> Result<#alloc::sync::Arc<Self>, D::Error>
+					where
+						D: ::serde::de::Deserializer<'de>
+					{
+						<#alloc::boxed::Box<Self> as ::serde::Deserialize<'de>>::deserialize(deserializer).map(Into::into)
+					}
 
-		#serialize
+					/// Deserializes a validated
+					#[doc = #name]
+					/// into a new reference-counted allocation.
+					pub fn deserialize_rc<'de, D>(deserializer: D) -> Result<#alloc::rc::Rc<Self>, D::Error>
+					where
+						D: ::serde::de::Deserializer<'de>
+					{
+						<#alloc::boxed::Box<Self> as ::serde::Deserialize<'de>>::deserialize(deserializer).map(Into::into)
+					}
+				}
+			}
+		});
 
-		#deserialize
+		let rc = quote! {
+			impl #ident {
+				/// Copies this
+				#[doc = #name]
+				/// into a new atomically reference-counted allocation.
+				pub fn to_arc(&self) -> #alloc::sync::Arc<Self> {
+					let arc: #alloc::sync::Arc<str> = self.as_str().into();
+					unsafe { #alloc::sync::Arc::from_raw(#alloc::sync::Arc::into_raw(arc) as *const Self) }
+				}
 
-		#owned_type
-	}
-}
+				/// Copies this
+				#[doc = #name]
+				/// into a new reference-counted allocation.
+				pub fn to_rc(&self) -> #alloc::rc::Rc<Self> {
+					let rc: #alloc::rc::Rc<str> = self.as_str().into();
+					unsafe { #alloc::rc::Rc::from_raw(#alloc::rc::Rc::into_raw(rc) as *const Self) }
+				}
+			}
+
+			impl From<&#ident> for #alloc::sync::Arc<#ident> {
+				fn from(value: &#ident) -> Self {
+					value.to_arc()
+				}
+			}
+
+			impl From<&#ident> for #alloc::rc::Rc<#ident> {
+				fn from(value: &#ident) -> Self {
+					value.to_rc()
+				}
+			}
+
+			#from_owned
+
+			#deserialize
+		};
+
+		require_alloc(
+			rc,
+			"the `rc` attribute requires the `alloc` feature of `str-newtype`",
+		)
+	});
+
+	let gen_tests = options.gen_tests.then(|| {
+		let owned = options.owned.as_ref().map(|owned| {
+			let owned_ident = &owned.ident;
+			cfg_alloc(quote!(assert_auto_traits::<#owned_ident>();))
+		});
+
+		let error = error
+			.as_ref()
+			.map(|error| quote!(assert_auto_traits::<#error<&'static str>>();));
+
+		quote! {
+			#[cfg(test)]
+			const _: () = {
+				const fn assert_auto_traits<T: ?Sized + Send + Sync + Unpin>() {}
+				assert_auto_traits::<#ident>();
+				#owned
+				#error
+			};
+		}
+	});
+
+	let with_char = {
+		let output = match &error {
+			Some(error) => quote!(Result<&Self, #error<&str>>),
+			None => quote!(&Self),
+		};
 
-fn partial_eq_impl(ident: &syn::Ident, ty: &syn::Type, fallible: bool) -> TokenStream {
-	if fallible {
 		quote! {
-			impl PartialEq<#ty> for #ident {
-				fn eq(&self, other: &#ty) -> bool {
-					match Self::new(other) {
-						Ok(other) => self == other,
-						Err(_) => false
+			impl #ident {
+				/// Calls the given function with the
+				#[doc = #name]
+				/// made of the given single character, or the validation error.
+				///
+				/// The character is encoded in a stack buffer, hence no allocation
+				/// is performed.
+				pub fn with_char<R>(c: char, f: impl FnOnce(#output) -> R) -> R {
+					let mut buffer = [0; 4];
+					f(Self::from_str(c.encode_utf8(&mut buffer)))
+				}
+			}
+		}
+	};
+
+	let constructor = match error {
+		Some(error) => {
+			let (reason_param, reason_arg, reason_field) = match &options.validate_result {
+				Some(ty) => (
+					Some(quote!(, E = #ty)),
+					Some(quote!(, E)),
+					Some(quote!(, pub Option<E>)),
+				),
+				None => (None, None, None),
+			};
+			let reason_bound = |bound: TokenStream| {
+				options
+					.validate_result
+					.as_ref()
+					.map(|_| quote!(, E: #bound))
+			};
+			let reason_debug = reason_bound(quote!(::core::fmt::Debug));
+			let reason_display = reason_bound(quote!(::core::fmt::Display));
+			let reason_error = reason_bound(quote!(::core::fmt::Debug + ::core::fmt::Display));
+
+			let (debug_reason, display_reason) = match &options.validate_result {
+				Some(_) => (
+					quote! {
+						write!(f, ", ")?;
+						self.1.fmt(f)?;
+					},
+					quote! {
+						if let Some(reason) = &self.1 {
+							write!(f, " ({reason})")?;
+						}
+					},
+				),
+				None => (quote!(), quote!()),
+			};
+
+			let (debug_input, display_input) = if options.secret {
+				(quote!(f.write_str("[REDACTED]")?;), quote!())
+			} else {
+				(
+					quote!(self.0.fmt(f)?;),
+					quote! {
+						write!(f, ": ")?;
+						self.0.fmt(f)?;
+					},
+				)
+			};
+
+			let suggestion = options.normalize.then(|| {
+				cfg_alloc(quote! {
+					impl<T: AsRef<[u8]> #reason_arg> #error<T #reason_arg> {
+						/// Returns the canonical form of the invalid input, if it
+						/// is a valid
+						#[doc = concat!(#name, ".")]
+						///
+						/// This can be used to suggest a valid alternative to the
+						/// rejected input.
+						pub fn suggestion(&self) -> Option<#alloc::string::String> {
+							let input = ::core::str::from_utf8(self.0.as_ref()).ok()?;
+							let normalized = #ident::normalize(input);
+							#ident::from_str(&normalized).is_ok().then_some(normalized)
+						}
+					}
+				})
+			});
+
+			let default = options.error_default.map(|_| {
+				let reason_value = options.validate_result.as_ref().map(|_| quote!(, None));
+				quote! {
+					impl<T: ::core::default::Default #reason_arg> ::core::default::Default for #error<T #reason_arg> {
+						fn default() -> Self {
+							Self(T::default() #reason_value)
+						}
+					}
+				}
+			});
+
+			let err = |reason| {
+				let error = error_value(options, &error, quote!(input), reason);
+				quote!(Err(#error))
+			};
+			let new = validate(
+				&ident,
+				options,
+				Input::Bytes(quote!(bytes)),
+				&quote!(Ok(unsafe { Self::new_unchecked_from_bytes(bytes) })),
+				err,
+			);
+			let from_bytes = validate(
+				&ident,
+				options,
+				Input::Bytes(quote!(input)),
+				&quote!(Ok(unsafe { Self::new_unchecked_from_bytes(input) })),
+				err,
+			);
+			let from_str = validate(
+				&ident,
+				options,
+				Input::Str(quote!(input)),
+				&quote!(Ok(unsafe { Self::new_unchecked(input) })),
+				err,
+			);
+
+			let trait_impls = error_impls(options, &error);
+			let static_panic_msg = format!("invalid `{ident}` literal");
+
+			let error_struct = |error_default: Option<TokenStream>| {
+				quote! {
+					/// Invalid
+					#[doc = #name]
+					/// error.
+					///
+					/// This error is raised by the
+					#[doc = #new_method_link]
+					/// when the input is not a valid
+					#[doc = concat!(#name, ".")]
+					pub struct #error<T #error_default #reason_param>(pub T #reason_field);
+				}
+			};
+			let error_struct = cfg_alloc_else(
+				error_struct(Some(quote!(= #alloc::string::String))),
+				error_struct(None),
+			);
+
+			quote! {
+				#error_struct
+
+				impl<T: ::core::fmt::Debug #reason_debug> ::core::fmt::Debug for #error<T #reason_arg> {
+					fn fmt(&self, f: &mut core::fmt::Formatter) -> ::core::fmt::Result {
+						f.write_str(#debug_name)?;
+						write!(f, "(")?;
+						#debug_input
+						#debug_reason
+						write!(f, ")")
+					}
+				}
+
+				impl<T: ::core::fmt::Display #reason_display> ::core::fmt::Display for #error<T #reason_arg> {
+					fn fmt(&self, f: &mut core::fmt::Formatter) -> ::core::fmt::Result {
+						write!(f, "invalid ")?;
+						f.write_str(#name)?;
+						#display_input
+						#display_reason
+						Ok(())
+					}
+				}
+
+				impl<T: ::core::fmt::Debug + ::core::fmt::Display #reason_error> ::core::error::Error for #error<T #reason_arg> {}
+
+				#trait_impls
+
+				#suggestion
+
+				#default
+
+				impl #ident {
+					/// Creates a new
+					#[doc = #name]
+					/// by parsing the input value.
+					pub fn new<T: ?Sized + AsRef<[u8]>>(input: &T) -> Result<&Self, #error<&T>> {
+						let bytes = input.as_ref();
+						#new
+					}
+
+					/// Creates a new
+					#[doc = #name]
+					/// by parsing the input bytes.
+					pub const fn from_bytes(input: &[u8]) -> Result<&Self, #error<&[u8]>> {
+						#from_bytes
+					}
+
+					/// Creates a new
+					#[doc = #name]
+					/// by parsing the input string.
+					pub const fn from_str(input: &str) -> Result<&Self, #error<&str>> {
+						#from_str
+					}
+
+					/// Creates a new
+					#[doc = #name]
+					/// from a static string.
+					///
+					/// # Panics
+					///
+					/// Panics if the input is not a valid
+					#[doc = concat!(#name, ".")]
+					/// When evaluated in a `const` context (such as a `const` item
+					/// or block), this is a compile-time error.
+					pub const fn from_static(input: &'static str) -> &'static Self {
+						match Self::from_str(input) {
+							Ok(value) => value,
+							Err(_) => panic!(#static_panic_msg)
+						}
+					}
+
+					/// Checks that the input string is a valid
+					#[doc = concat!(#name, ",")]
+					/// including the checks performed by the constructors on top
+					/// of the `validate_*` methods (such as `len`).
+					pub const fn is_valid(input: &str) -> bool {
+						Self::from_str(input).is_ok()
+					}
+
+					/// Creates a new
+					#[doc = #name]
+					/// from the input bytes without validation.
+					///
+					/// # Safety
+					/// The input bytes must be a valid
+					#[doc = concat!(#name, ".")]
+					pub const unsafe fn new_unchecked_from_bytes(input: &[u8]) -> &Self {
+						unsafe { ::core::mem::transmute::<&[u8], &Self>(input) }
+					}
+
+					/// Creates a new
+					#[doc = #name]
+					/// from the input string without validation.
+					///
+					/// # Safety
+					/// The input string must be a valid
+					#[doc = concat!(#name, ".")]
+					pub const unsafe fn new_unchecked(input: &str) -> &Self {
+						unsafe { Self::new_unchecked_from_bytes(input.as_bytes()) }
+					}
+				}
+
+				impl<'a> TryFrom<&'a [u8]> for &'a #ident {
+					type Error = #error<&'a [u8]>;
+
+					fn try_from(value: &'a[u8]) -> Result<&'a #ident, #error<&'a [u8]>> {
+						#ident::new(value)
+					}
+				}
+
+				impl<'a> TryFrom<&'a str> for &'a #ident {
+					type Error = #error<&'a str>;
+
+					fn try_from(value: &'a str) -> Result<&'a #ident, #error<&'a str>> {
+						#ident::new(value)
 					}
 				}
 			}
+		}
+		None => {
+			quote! {
+				impl #ident {
+					/// Creates a new
+					#[doc = #name]
+					/// by parsing the input value.
+					pub fn new<T: ?Sized + AsRef<str>>(input: &T) -> &Self {
+						Self::from_str(input.as_ref())
+					}
+
+					/// Creates a new
+					#[doc = #name]
+					/// by parsing the input bytes.
+					pub const fn from_bytes(input: &[u8]) -> Result<&Self, ::core::str::Utf8Error> {
+						match ::core::str::from_utf8(input) {
+							Ok(s) => Ok(unsafe { Self::from_str(s) }),
+							Err(e) => Err(e)
+						}
+					}
+
+					/// Creates a new
+					#[doc = #name]
+					/// by parsing the input string.
+					pub const fn from_str(input: &str) -> &Self {
+						unsafe { ::core::mem::transmute::<&str, &Self>(input) }
+					}
+				}
+
+				impl<'a> TryFrom<&'a [u8]> for &'a #ident {
+					type Error = ::core::str::Utf8Error;
 
-			impl PartialEq<#ident> for #ty {
-				fn eq(&self, other: &#ident) -> bool {
-					match #ident::new(self) {
-						Ok(this) => this == other,
-						Err(_) => false
+					fn try_from(value: &'a[u8]) -> Result<&'a #ident, ::core::str::Utf8Error> {
+						#ident::from_bytes(value)
+					}
+				}
+
+				impl<'a> From<&'a str> for &'a #ident {
+					fn from(value: &'a str) -> &'a #ident {
+						#ident::new(value)
 					}
 				}
 			}
 		}
-	} else {
+	};
+
+	let empty = options.empty.then(|| {
+		let panic_msg = format!("the empty string is not a valid `{ident}`");
+		let from_str = if options.infallible {
+			quote!(Self::from_str(""))
+		} else {
+			quote! {
+				match Self::from_str("") {
+					Ok(value) => value,
+					Err(_) => panic!(#panic_msg)
+				}
+			}
+		};
+
 		quote! {
-			impl PartialEq<#ty> for #ident {
-				fn eq(&self, other: &#ty) -> bool {
-					self == Self::new(other)
+			impl #ident {
+				/// Empty
+				#[doc = concat!(#name, ".")]
+				pub const EMPTY: &'static Self = #from_str;
+			}
+
+			// Checks that the empty string is valid at compile time.
+			const _: () = {
+				let _ = #ident::EMPTY;
+			};
+
+			impl ::core::default::Default for &#ident {
+				fn default() -> Self {
+					#ident::EMPTY
+				}
+			}
+		}
+	});
+
+	let char_table = options.char_table.then(|| {
+		let from_str = if options.infallible {
+			quote! {
+				Some(Self::from_str(s))
+			}
+		} else {
+			quote! {
+				match Self::from_str(s) {
+					Ok(value) => Some(value),
+					Err(_) => None
 				}
 			}
+		};
+
+		quote! {
+			impl #ident {
+				/// Returns the single character
+				#[doc = #name]
+				/// matching the given character, if any.
+				///
+				/// The returned value is borrowed from a static table of all the
+				/// ASCII characters, hence no allocation is performed. Non-ASCII
+				/// characters are not part of the table and always return `None`.
+				pub const fn from_char(c: char) -> Option<&'static Self> {
+					const TABLE: &[u8; 128] = &{
+						let mut table = [0u8; 128];
+						let mut i = 0;
+						while i < 128 {
+							table[i] = i as u8;
+							i += 1
+						}
+						table
+					};
+
+					if c.is_ascii() {
+						let bytes = ::core::slice::from_ref(&TABLE[c as usize]);
+						let s = unsafe { ::core::str::from_utf8_unchecked(bytes) };
+						#from_str
+					} else {
+						None
+					}
+				}
+
+				/// All the valid single character
+				#[doc = #name]
+				/// values, in ascending order.
+				///
+				/// Only ASCII characters are considered (see [`Self::from_char`]).
+				pub const ALL: &'static [&'static Self] = {
+					const LEN: usize = {
+						let mut len = 0;
+						let mut c = 0u8;
+						while c < 128 {
+							if #ident::from_char(c as char).is_some() {
+								len += 1
+							}
+							c += 1
+						}
+						len
+					};
+
+					const VALUES: [&'static #ident; LEN] = {
+						// Placeholder, overwritten by the loop below.
+						let empty = unsafe { ::core::mem::transmute::<&str, &#ident>("") };
+						let mut values = [empty; LEN];
+						let mut i = 0;
+						let mut c = 0u8;
+						while c < 128 {
+							if let Some(value) = #ident::from_char(c as char) {
+								values[i] = value;
+								i += 1
+							}
+							c += 1
+						}
+						values
+					};
 
-			impl PartialEq<#ident> for #ty {
-				fn eq(&self, other: &#ident) -> bool {
-					#ident::new(self) == other
+					&VALUES
+				};
+
+				/// Returns an iterator over the single character
+				#[doc = #name]
+				/// values (see [`Self::ALL`]) between `from` and `to`
+				/// (inclusive), in ascending order.
+				pub fn range<'a>(
+					from: &'a Self,
+					to: &'a Self
+				) -> impl Iterator<Item = &'static Self> + 'a {
+					Self::ALL
+						.iter()
+						.copied()
+						.skip_while(move |value| value.as_bytes() < from.as_bytes())
+						.take_while(move |value| value.as_bytes() <= to.as_bytes())
 				}
 			}
 		}
+	});
+
+	let hash = options.hash.as_ref().filter(|h| h.std).map(|_| {
+		quote! {
+			impl ::core::hash::Hash for #ident {
+				fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+					<str as ::core::hash::Hash>::hash(self.as_str(), state)
+				}
+			}
+		}
+	});
+
+	let fx_hash = options.hash.as_ref().filter(|h| h.fxhash).map(|_| {
+		quote! {
+			impl #ident {
+				/// Computes a fast, non DoS-resistant hash of the
+				#[doc = concat!(#name, ".")]
+				///
+				/// The result is the same as hashing the underlying `str` with
+				/// an `FxHasher`. It must only be used on trusted inputs, for
+				/// instance to key internal caches.
+				pub fn fx_hash(&self) -> u64 {
+					::core::hash::BuildHasher::hash_one(
+						&str_newtype::rustc_hash::FxBuildHasher,
+						self.as_str()
+					)
+				}
+			}
+		}
+	});
+
+	let validate_len = options.len.as_ref().map(|len| {
+		let validate_len = validate_len(&name, len);
+		quote! {
+			impl #ident {
+				#validate_len
+			}
+		}
+	});
+
+	quote! {
+		#constructor
+
+		#validate_len
+
+		#with_char
+
+		#char_table
+		#empty
+
+		#fx_hash
+
+		#gen_tests
+
+		impl #ident {
+			/// Returns the
+			#[doc = #name]
+			/// as a string.
+			pub const fn as_str(&self) -> &str {
+				&self.0
+			}
+
+			/// Returns the
+			#[doc = #name]
+			/// as a byte string.
+			pub const fn as_bytes(&self) -> &[u8] {
+				self.0.as_bytes()
+			}
+
+			/// Returns a raw pointer to the first byte of the
+			#[doc = concat!(#name, ".")]
+			///
+			/// Along with [`Self::len`], this is useful to pass the value
+			/// across FFI boundaries.
+			pub const fn as_ptr(&self) -> *const u8 {
+				self.0.as_ptr()
+			}
+
+			/// Returns the length of the
+			#[doc = #name]
+			/// in bytes.
+			pub const fn len(&self) -> usize {
+				self.0.len()
+			}
+
+			/// Checks if the
+			#[doc = #name]
+			/// is empty.
+			pub const fn is_empty(&self) -> bool {
+				self.0.is_empty()
+			}
+
+			/// Checks that the
+			#[doc = #name]
+			/// is equal to the given string.
+			///
+			/// Contrarily to `==`, this function can be used in `const`
+			/// contexts.
+			pub const fn const_eq(&self, other: &str) -> bool {
+				let a = self.as_bytes();
+				let b = other.as_bytes();
+
+				if a.len() != b.len() {
+					return false;
+				}
+
+				let mut i = 0;
+				while i < a.len() {
+					if a[i] != b[i] {
+						return false;
+					}
+
+					i += 1
+				}
+
+				true
+			}
+		}
+
+		impl AsRef<#ident> for #ident {
+			fn as_ref(&self) -> &#ident {
+				self
+			}
+		}
+
+		impl AsRef<str> for #ident {
+			fn as_ref(&self) -> &str {
+				self.as_str()
+			}
+		}
+
+		impl AsRef<[u8]> for #ident {
+			fn as_ref(&self) -> &[u8] {
+				self.as_str().as_bytes()
+			}
+		}
+
+		impl ::core::fmt::Display for #ident {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				#fmt_value
+			}
+		}
+
+		impl ::core::fmt::Debug for #ident {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				#fmt_value
+			}
+		}
+
+		impl ::core::borrow::Borrow<str> for #ident {
+			fn borrow(&self) -> &str {
+				self.as_str()
+			}
+		}
+
+		impl<'a> From<&'a #ident> for &'a str {
+			fn from(value: &'a #ident) -> Self {
+				value.as_str()
+			}
+		}
+
+		impl<'a> From<&'a #ident> for &'a [u8] {
+			fn from(value: &'a #ident) -> Self {
+				value.as_bytes()
+			}
+		}
+
+		#cow
+		#rc
+
+		#(#subtypes)*
+
+		#(#derives)*
+
+		#ascii
+		#case_insensitive
+
+		#deref
+
+		#(#eq)*
+		#string_eq
+		#hash
+
+		#(#ord)*
+
+		#serialize
+
+		#deserialize
+
+		#schemars
+
+		#diesel
+
+		#borsh
+
+		#serde_json
+
+		#os
+
+		#owned_type
+
+		#list_type
+		#staging_type
+	}
+}
+
+/// Validated input of a generated constructor.
+enum Input {
+	/// Byte string, checked to be valid UTF-8 before anything else.
+	Bytes(TokenStream),
+
+	/// String.
+	Str(TokenStream),
+
+	/// Byte string of a `[u8]` new-type, without UTF-8 check.
+	Binary(TokenStream),
+}
+
+/// Generates an expression validating the given input, evaluating to `ok`
+/// if the input is valid, and to `err(reason)` otherwise.
+///
+/// The built-in checks (UTF-8 validity, `ascii` and `len`) are performed before
+/// calling the `validate_*` methods of the type. The failure reason is only
+/// relevant with the `validate_result` sub-attribute, in which case it is an
+/// `Option<E>` expression, `None` meaning that a built-in check failed.
+fn validate(
+	ident: &syn::Ident,
+	options: &Options,
+	input: Input,
+	ok: &TokenStream,
+	err: impl Fn(TokenStream) -> TokenStream,
+) -> TokenStream {
+	let mut checks = Vec::new();
+
+	let (bytes, validate) = match input {
+		Input::Bytes(bytes) => {
+			checks.push(quote!(::core::str::from_utf8(#bytes).is_ok()));
+			let validate = quote!(#ident::validate_bytes(#bytes));
+			(bytes, validate)
+		}
+		Input::Str(str) => {
+			let validate = quote!(#ident::validate_str(#str));
+			(quote!(<str>::as_bytes(#str)), validate)
+		}
+		Input::Binary(bytes) => {
+			let validate = quote!(#ident::validate_bytes(#bytes));
+			(bytes, validate)
+		}
+	};
+
+	if options.ascii.is_some() {
+		checks.push(quote!(<[u8]>::is_ascii(#bytes)))
+	}
+
+	if options.len.is_some() {
+		checks.push(quote!(#ident::validate_len(#bytes)))
+	}
+
+	if options.validate_result.is_some() {
+		let err_reason = err(quote!(Some(reason)));
+		let body = quote! {
+			match #validate {
+				Ok(()) => {
+					#ok
+				}
+				Err(reason) => {
+					#err_reason
+				}
+			}
+		};
+
+		if checks.is_empty() {
+			body
+		} else {
+			let err_none = err(quote!(None));
+			quote! {
+				if #(#checks)&&* {
+					#body
+				} else {
+					#err_none
+				}
+			}
+		}
+	} else {
+		if !options.no_validate {
+			checks.push(validate)
+		}
+
+		if checks.is_empty() {
+			ok.clone()
+		} else {
+			let err = err(quote!(None));
+			quote! {
+				if #(#checks)&&* {
+					#ok
+				} else {
+					#err
+				}
+			}
+		}
+	}
+}
+
+/// Generates the `validate_len` method checking the `len` or `exact_len`
+/// sub-attribute.
+fn validate_len(name: &str, len: &LenOptions) -> TokenStream {
+	let min = len.min;
+	let doc = if len.is_exact() {
+		"has the exact byte length"
+	} else {
+		"has a valid byte length"
+	};
+	let check = match len.max {
+		Some(max) if max == min => quote!(input.len() == #min),
+		Some(max) if min == 0 => quote!(input.len() <= #max),
+		Some(max) => quote!(input.len() >= #min && input.len() <= #max),
+		None => quote!(input.len() >= #min),
+	};
+
+	quote! {
+		/// Checks that the input
+		#[doc = #doc]
+		/// of a
+		#[doc = concat!(#name, ".")]
+		///
+		/// This check is performed by the constructors in addition to the
+		/// validation methods.
+		pub const fn validate_len(input: &[u8]) -> bool {
+			#check
+		}
+	}
+}
+
+/// Generates an error value for the given invalid input and failure reason
+/// (ignored without the `validate_result` sub-attribute).
+fn error_value(
+	options: &Options,
+	error: &syn::Ident,
+	input: TokenStream,
+	reason: TokenStream,
+) -> TokenStream {
+	match &options.validate_result {
+		Some(ty) => quote!(#error::<_, #ty>(#input, #reason)),
+		None => quote!(#error(#input)),
+	}
+}
+
+/// Generates a closure mapping an error of the borrowed type into an error
+/// on the given input, preserving the failure reason.
+fn map_error(options: &Options, error: &syn::Ident, input: TokenStream) -> TokenStream {
+	match &options.validate_result {
+		Some(ty) => quote!(|e| #error::<_, #ty>(#input, e.1)),
+		None => quote!(|_| #error(#input)),
+	}
+}
+
+/// Generates the input accessors of the error type, and its `Clone`,
+/// `PartialEq`, `Eq` and `Hash` implementations, conditional on the same
+/// implementations for the input (and failure reason) types.
+fn error_impls(options: &Options, error: &syn::Ident) -> TokenStream {
+	let (reason_arg, map, clone, eq, hash) = match &options.validate_result {
+		Some(_) => (
+			Some(quote!(, E)),
+			quote!(#error(f(self.0), self.1)),
+			quote!(Self(self.0.clone(), self.1.clone())),
+			quote!(self.0 == other.0 && self.1 == other.1),
+			quote! {
+				::core::hash::Hash::hash(&self.0, state);
+				::core::hash::Hash::hash(&self.1, state)
+			},
+		),
+		None => (
+			None,
+			quote!(#error(f(self.0))),
+			quote!(Self(self.0.clone())),
+			quote!(self.0 == other.0),
+			quote!(::core::hash::Hash::hash(&self.0, state)),
+		),
+	};
+	let reason_bound = |bound: TokenStream| {
+		options
+			.validate_result
+			.as_ref()
+			.map(|_| quote!(, E: #bound))
+	};
+	let reason_clone = reason_bound(quote!(::core::clone::Clone));
+	let reason_eq = reason_bound(quote!(::core::cmp::PartialEq));
+	let reason_total_eq = reason_bound(quote!(::core::cmp::Eq));
+	let reason_hash = reason_bound(quote!(::core::hash::Hash));
+
+	quote! {
+		impl<T #reason_arg> #error<T #reason_arg> {
+			/// Returns the invalid input.
+			pub fn into_inner(self) -> T {
+				self.0
+			}
+
+			/// Returns a reference to the invalid input.
+			pub const fn as_inner(&self) -> &T {
+				&self.0
+			}
+
+			/// Maps the invalid input, preserving the failure reason, if any.
+			///
+			/// This can be used to turn an error on a borrowed input into an
+			/// error on an owned input.
+			pub fn map<U>(self, f: impl FnOnce(T) -> U) -> #error<U #reason_arg> {
+				#map
+			}
+		}
+
+		impl<T: ::core::clone::Clone #reason_clone> ::core::clone::Clone for #error<T #reason_arg> {
+			fn clone(&self) -> Self {
+				#clone
+			}
+		}
+
+		impl<T: ::core::cmp::PartialEq #reason_eq> ::core::cmp::PartialEq for #error<T #reason_arg> {
+			fn eq(&self, other: &Self) -> bool {
+				#eq
+			}
+		}
+
+		impl<T: ::core::cmp::Eq #reason_total_eq> ::core::cmp::Eq for #error<T #reason_arg> {}
+
+		impl<T: ::core::hash::Hash #reason_hash> ::core::hash::Hash for #error<T #reason_arg> {
+			fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+				#hash
+			}
+		}
+	}
+}
+
+/// Types that the new-type can always be compared to, regardless of the `eq`
+/// sub-attribute, apart from `String` which requires the `alloc` feature.
+fn builtin_eq_types() -> Vec<syn::Type> {
+	vec![syn::parse_quote!(str), syn::parse_quote!(&str)]
+}
+
+fn partial_eq_impl(ident: &syn::Ident, ty: &syn::Type) -> TokenStream {
+	quote! {
+		impl PartialEq<#ty> for #ident {
+			fn eq(&self, other: &#ty) -> bool {
+				self.as_bytes() == <#ty as AsRef<[u8]>>::as_ref(other)
+			}
+		}
+
+		impl PartialEq<#ident> for #ty {
+			fn eq(&self, other: &#ident) -> bool {
+				<#ty as AsRef<[u8]>>::as_ref(self) == other.as_bytes()
+			}
+		}
+	}
+}
+
+fn partial_ord_impl(ident: &syn::Ident, ty: &syn::Type) -> TokenStream {
+	quote! {
+		impl PartialOrd<#ty> for #ident {
+			fn partial_cmp(&self, other: &#ty) -> Option<::core::cmp::Ordering> {
+				self.as_bytes().partial_cmp(<#ty as AsRef<[u8]>>::as_ref(other))
+			}
+		}
+
+		impl PartialOrd<#ident> for #ty {
+			fn partial_cmp(&self, other: &#ident) -> Option<::core::cmp::Ordering> {
+				<#ty as AsRef<[u8]>>::as_ref(self).partial_cmp(other.as_bytes())
+			}
+		}
+	}
+}
+
+fn derive_owned_type(
+	name: &str,
+	ident: &syn::Ident,
+	options: &Options,
+	owned: &OwnedTypeOptions,
+	error: Option<&syn::Ident>,
+) -> TokenStream {
+	let alloc = alloc_path();
+	let foreign = &options.foreign;
+	let as_ref = owned
+		.as_ref
+		.clone()
+		.unwrap_or_else(|| format_ident!("as_{}", SnakeCase(&ident.to_string())));
+	let owned_ident = &owned.ident;
+
+	let fmt_value = if options.secret {
+		let placeholder = format!("[REDACTED {owned_ident}]");
+		quote!(f.write_str(#placeholder))
+	} else {
+		quote!(f.write_str(&self.0))
+	};
+
+	let derives = owned
+		.derives
+		.iter()
+		.filter(|d| !(options.empty && matches!(d, Derive::Default)))
+		.map(|d| d.generate(ident, owned_ident, &as_ref, foreign));
+
+	let collections = options.collections.map(|_| {
+		let map = format_ident!("{owned_ident}Map");
+		let set = format_ident!("{owned_ident}Set");
+		let std = std_path();
+
+		require_std(
+			quote! {
+				/// Hash map keyed by
+				#[doc = concat!(#name, ".")]
+				pub type #map<V, S = #std::hash::RandomState> = #std::collections::HashMap<#owned_ident, V, S>;
+
+				/// Hash set of
+				#[doc = concat!(#name, ".")]
+				pub type #set<S = #std::hash::RandomState> = #std::collections::HashSet<#owned_ident, S>;
+			},
+			"the `collections` attribute requires the `std` feature of `str-newtype`",
+		)
+	});
+
+	let concat = options.concat.map(|_| {
+		quote! {
+			impl ::core::ops::Add<&#ident> for #owned_ident {
+				type Output = Self;
+
+				/// Appends `rhs`, reusing the buffer of `self`.
+				#[inline]
+				fn add(mut self, rhs: &#ident) -> Self {
+					self += rhs;
+					self
+				}
+			}
+
+			impl ::core::ops::AddAssign<&#ident> for #owned_ident {
+				/// Appends `rhs` without validation, since the `concat`
+				/// attribute asserts that the type is closed under
+				/// concatenation.
+				#[inline]
+				fn add_assign(&mut self, rhs: &#ident) {
+					self.0.push_str(rhs.as_str())
+				}
+			}
+		}
+	});
+
+	let empty = options.empty.then(|| {
+		quote! {
+			impl #owned_ident {
+				/// Creates a new empty owned
+				#[doc = concat!(#name, ".")]
+				///
+				/// This does not allocate.
+				pub const fn new_empty() -> Self {
+					Self(#alloc::string::String::new())
+				}
+			}
+
+			impl ::core::default::Default for #owned_ident {
+				fn default() -> Self {
+					Self::new_empty()
+				}
+			}
+		}
+	});
+
+	let constructor = match error {
+		Some(error) => {
+			let err = |input, reason| {
+				let error = error_value(options, error, input, reason);
+				quote!(Err(#error))
+			};
+
+			let append = |push: TokenStream| {
+				if options.normalize {
+					quote! {
+						self.try_mutate(|buffer| #push)
+					}
+				} else {
+					let validate = validate(
+						ident,
+						options,
+						Input::Str(quote!(self.0.as_str())),
+						&quote!(Ok(())),
+						|reason| {
+							let err = err(quote!(rejected), reason);
+							quote! {{
+								let rejected = self.0.clone();
+								self.0.truncate(len);
+								#err
+							}}
+						},
+					);
+					quote! {
+						let len = self.0.len();
+						let buffer = &mut self.0;
+						#push;
+						#validate
+					}
+				}
+			};
+
+			// Mutations do not trim the buffer (which would for instance
+			// discard pushed whitespace), only constructors do.
+			let (commit, new_untrimmed) = if options.trim {
+				let body = if options.normalize {
+					let validate = validate(
+						ident,
+						options,
+						Input::Str(quote!(&normalized)),
+						&quote!(Ok(Self(normalized))),
+						|reason| err(quote!(input), reason),
+					);
+					quote! {
+						let normalized = #ident::normalize(&input);
+						#validate
+					}
+				} else {
+					validate(
+						ident,
+						options,
+						Input::Str(quote!(input.as_str())),
+						&quote!(Ok(Self(input))),
+						|reason| err(quote!(input), reason),
+					)
+				};
+
+				(
+					quote!(Self::new_untrimmed),
+					Some(quote! {
+						/// Validates the input string without trimming it.
+						fn new_untrimmed(input: #alloc::string::String) -> Result<Self, #error> {
+							#body
+						}
+					}),
+				)
+			} else {
+				(quote!(Self::from_string), None)
+			};
+
+			let try_push_str = append(quote!(buffer.push_str(s)));
+			let append_all = append(quote! {
+				for s in iter {
+					buffer.push_str(s.as_ref())
+				}
+			});
+
+			let (new, try_from_array, try_from_ref) = if options.normalize || options.trim {
+				let invalid = err(quote!(input), quote!(None));
+				let map_error = map_error(options, error, quote!(value));
+				let new = if options.normalize {
+					let validate = validate(
+						ident,
+						options,
+						Input::Str(quote!(&normalized)),
+						&quote!(Ok(Self(normalized))),
+						|reason| err(quote!(input), reason),
+					);
+					let s = if options.trim {
+						quote!(s.trim())
+					} else {
+						quote!(s)
+					};
+					quote! {
+						match ::core::str::from_utf8(input.as_bytes()) {
+							Ok(s) => {
+								let normalized = #ident::normalize(#s);
+								#validate
+							}
+							Err(_) => #invalid
+						}
+					}
+				} else {
+					let validate = validate(
+						ident,
+						options,
+						Input::Str(quote!(trimmed)),
+						&quote! {{
+							let mut bytes = input.into_bytes();
+							bytes.truncate(end);
+							bytes.drain(..start);
+							Ok(unsafe { Self::new_unchecked(bytes) })
+						}},
+						|reason| err(quote!(input), reason),
+					);
+					quote! {
+						match ::core::str::from_utf8(input.as_bytes()) {
+							Ok(s) => {
+								let start = s.len() - s.trim_start().len();
+								let trimmed = s[start..].trim_end();
+								let end = start + trimmed.len();
+								#validate
+							}
+							Err(_) => #invalid
+						}
+					}
+				};
+				(
+					new,
+					quote! {
+						Self::new(value.to_vec()).map_err(#map_error)
+					},
+					quote! {
+						Self::new(#alloc::borrow::ToOwned::to_owned(value)).map_err(#map_error)
+					},
+				)
+			} else {
+				(
+					{
+						let validate = validate(
+							ident,
+							options,
+							Input::Bytes(quote!(bytes)),
+							&quote!(Ok(unsafe { Self::new_unchecked(input.into_bytes()) })),
+							|reason| err(quote!(input), reason),
+						);
+						quote! {
+							let bytes = input.as_bytes();
+							#validate
+						}
+					},
+					validate(
+						ident,
+						options,
+						Input::Bytes(quote!(&value)),
+						&quote!(Ok(unsafe { Self::new_unchecked(value) })),
+						|reason| err(quote!(value), reason),
+					),
+					quote! {
+						#ident::new(value).map(#alloc::borrow::ToOwned::to_owned)
+					},
+				)
+			};
+
+			quote! {
+				impl #owned_ident {
+					/// Creates a new owned
+					#[doc = #name]
+					/// by parsing the input value.
+					pub fn new<T: str_newtype::Buffer>(input: T) -> Result<Self, #error<T>> {
+						#new
+					}
+
+					/// Creates a new owned
+					#[doc = #name]
+					/// by parsing the input bytes.
+					pub fn from_bytes(input: #alloc::vec::Vec<u8>) -> Result<Self, #error<#alloc::vec::Vec<u8>>> {
+						Self::new(input)
+					}
+
+					/// Creates a new owned
+					#[doc = #name]
+					/// by parsing the input string.
+					pub fn from_string(input: #alloc::string::String) -> Result<Self, #error> {
+						Self::new(input)
+					}
+
+					/// Creates a new owned
+					#[doc = #name]
+					/// from a copy-on-write string.
+					///
+					/// The input is validated before being copied, which only
+					/// happens if it is borrowed: owned strings are reused.
+					pub fn from_cow(input: #alloc::borrow::Cow<'_, str>) -> Result<Self, #error<#alloc::borrow::Cow<'_, str>>> {
+						Self::new(input)
+					}
+
+					/// Joins the given
+					#[doc = #name]
+					/// values with the given separator.
+					///
+					/// The joined string is validated again since the grammar is
+					/// not assumed to be closed under concatenation.
+					pub fn try_join<I: IntoIterator<Item = Self>>(
+						iter: I,
+						sep: &str
+					) -> Result<Self, #error> {
+						let mut result = #alloc::string::String::new();
+
+						for (i, item) in iter.into_iter().enumerate() {
+							if i > 0 {
+								result.push_str(sep);
+							}
+
+							result.push_str(item.as_str());
+						}
+
+						Self::from_string(result)
+					}
+
+					/// Replaces all matches of `from` with `to`.
+					///
+					/// The replacement is performed on a copy that is validated
+					/// before being committed. If the result is not a valid
+					#[doc = #name]
+					/// then `self` is left unchanged and the rejected string is
+					/// returned in the error.
+					pub fn try_replace(&mut self, from: &str, to: &str) -> Result<(), #error> {
+						*self = #commit(self.0.replace(from, to))?;
+						Ok(())
+					}
+
+					/// Mutates the inner string with the given function.
+					///
+					/// The function is called on a copy that is validated
+					/// before being committed. If the result is not a valid
+					#[doc = #name]
+					/// then `self` is left unchanged and the rejected string is
+					/// returned in the error.
+					pub fn try_mutate<F: FnOnce(&mut #alloc::string::String)>(
+						&mut self,
+						f: F
+					) -> Result<(), #error> {
+						let mut s = self.0.clone();
+						f(&mut s);
+						*self = #commit(s)?;
+						Ok(())
+					}
+
+					#new_untrimmed
+
+					/// Appends the given string slice.
+					///
+					/// The whole buffer is validated again after the push. If the
+					/// result is not a valid
+					#[doc = #name]
+					/// then `self` is restored to its original value and the
+					/// rejected string is returned in the error.
+					pub fn try_push_str(&mut self, s: &str) -> Result<(), #error> {
+						#try_push_str
+					}
+
+					/// Appends the given character.
+					///
+					/// See [`Self::try_push_str`].
+					pub fn try_push(&mut self, c: char) -> Result<(), #error> {
+						self.try_push_str(c.encode_utf8(&mut [0; 4]))
+					}
+
+					/// Appends all the given string slices.
+					///
+					/// The buffer is validated only once, after every item has
+					/// been pushed. If the result is not a valid
+					#[doc = #name]
+					/// then `self` is restored to its original value and the
+					/// rejected string is returned in the error.
+					pub fn append_all<I: IntoIterator>(&mut self, iter: I) -> Result<(), #error>
+					where
+						I::Item: AsRef<str>,
+					{
+						#append_all
+					}
+
+					pub const fn #as_ref(&self) -> &#ident {
+						unsafe {
+							#ident::new_unchecked(self.0.as_str())
+						}
+					}
+				}
+
+				impl TryFrom<#alloc::vec::Vec<u8>> for #owned_ident {
+					type Error = #error<#alloc::vec::Vec<u8>>;
+
+					fn try_from(value: #alloc::vec::Vec<u8>) -> Result<Self, #error<#alloc::vec::Vec<u8>>> {
+						Self::new(value)
+					}
+				}
+
+				impl<const N: usize> TryFrom<[u8; N]> for #owned_ident {
+					type Error = #error<[u8; N]>;
+
+					fn try_from(value: [u8; N]) -> Result<Self, #error<[u8; N]>> {
+						#try_from_array
+					}
+				}
+
+				impl<'a> TryFrom<#alloc::borrow::Cow<'a, [u8]>> for #owned_ident {
+					type Error = #error<#alloc::borrow::Cow<'a, [u8]>>;
+
+					fn try_from(value: #alloc::borrow::Cow<'a, [u8]>) -> Result<Self, #error<#alloc::borrow::Cow<'a, [u8]>>> {
+						Self::new(value)
+					}
+				}
+
+				impl TryFrom<#alloc::string::String> for #owned_ident {
+					type Error = #error;
+
+					fn try_from(value: #alloc::string::String) -> Result<Self, #error> {
+						Self::new(value)
+					}
+				}
+
+				impl TryFrom<#alloc::boxed::Box<str>> for #owned_ident {
+					type Error = #error<#alloc::boxed::Box<str>>;
+
+					fn try_from(value: #alloc::boxed::Box<str>) -> Result<Self, #error<#alloc::boxed::Box<str>>> {
+						Self::new(value)
+					}
+				}
+
+				impl<'a> TryFrom<#alloc::borrow::Cow<'a, str>> for #owned_ident {
+					type Error = #error<#alloc::borrow::Cow<'a, str>>;
+
+					fn try_from(value: #alloc::borrow::Cow<'a, str>) -> Result<Self, #error<#alloc::borrow::Cow<'a, str>>> {
+						Self::new(value)
+					}
+				}
+
+				impl<'a> TryFrom<&'a str> for #owned_ident {
+					type Error = #error<&'a str>;
+
+					fn try_from(value: &'a str) -> Result<Self, #error<&'a str>> {
+						#try_from_ref
+					}
+				}
+
+				impl<'a> TryFrom<&'a #alloc::vec::Vec<u8>> for #owned_ident {
+					type Error = #error<&'a #alloc::vec::Vec<u8>>;
+
+					fn try_from(value: &'a #alloc::vec::Vec<u8>) -> Result<Self, #error<&'a #alloc::vec::Vec<u8>>> {
+						#try_from_ref
+					}
+				}
+
+				impl ::core::str::FromStr for #owned_ident {
+					type Err = #error;
+
+					fn from_str(value: &str) -> Result<Self, #error> {
+						Self::new(#alloc::borrow::ToOwned::to_owned(value))
+					}
+				}
+			}
+		}
+		None => {
+			let new = match (options.normalize, options.trim) {
+				(true, true) => quote! {
+					let input: #alloc::string::String = input.into();
+					Self(#ident::normalize(input.trim()))
+				},
+				(true, false) => quote! {
+					Self(#ident::normalize(&input.into()))
+				},
+				(false, true) => quote! {
+					let mut input: #alloc::string::String = input.into();
+					let end = input.trim_end().len();
+					input.truncate(end);
+					let start = input.len() - input.trim_start().len();
+					input.drain(..start);
+					Self(input)
+				},
+				(false, false) => quote! {
+					Self(input.into())
+				},
+			};
+
+			// Mutations do not trim the buffer (which would for instance
+			// discard pushed whitespace), only constructors do.
+			let commit = match (options.normalize, options.trim) {
+				(true, true) => quote!(Self(#ident::normalize(&s))),
+				(false, true) => quote!(Self(s)),
+				(_, false) => quote!(Self::from_string(s)),
+			};
+
+			quote! {
+				impl #owned_ident {
+					/// Creates a new owned
+					#[doc = #name]
+					/// by parsing the input value.
+					pub fn new(input: impl Into<#alloc::string::String>) -> Self {
+						#new
+					}
+
+					/// Creates a new owned
+					#[doc = #name]
+					/// by parsing the input string.
+					pub fn from_string(input: #alloc::string::String) -> Self {
+						Self::new(input)
+					}
+
+					/// Creates a new owned
+					#[doc = #name]
+					/// from a copy-on-write string, reusing owned strings.
+					pub fn from_cow(input: #alloc::borrow::Cow<'_, str>) -> Self {
+						Self::new(input)
+					}
+
+					/// Mutates the inner string with the given function.
+					pub fn mutate<F: FnOnce(&mut #alloc::string::String)>(&mut self, f: F) {
+						let mut s = ::core::mem::take(&mut self.0);
+						f(&mut s);
+						*self = #commit
+					}
+
+					/// Appends the given string slice.
+					pub fn push_str(&mut self, s: &str) {
+						self.mutate(|buffer| buffer.push_str(s))
+					}
+
+					/// Appends the given character.
+					pub fn push(&mut self, c: char) {
+						self.mutate(|buffer| buffer.push(c))
+					}
+
+					/// Creates a new owned
+					#[doc = #name]
+					/// by parsing the input bytes.
+					pub fn from_bytes(input: #alloc::vec::Vec<u8>) -> Result<Self, #alloc::string::FromUtf8Error> {
+						Ok(Self::new(#alloc::string::String::from_utf8(input)?))
+					}
+
+					pub fn #as_ref(&self) -> &#ident {
+						#ident::new(self.0.as_str())
+					}
+				}
+
+				impl TryFrom<#alloc::vec::Vec<u8>> for #owned_ident {
+					type Error = #alloc::string::FromUtf8Error;
+
+					fn try_from(value: #alloc::vec::Vec<u8>) -> Result<Self, #alloc::string::FromUtf8Error> {
+						Self::from_bytes(value)
+					}
+				}
+
+				impl<const N: usize> TryFrom<[u8; N]> for #owned_ident {
+					type Error = #alloc::string::FromUtf8Error;
+
+					fn try_from(value: [u8; N]) -> Result<Self, #alloc::string::FromUtf8Error> {
+						Self::from_bytes(value.into())
+					}
+				}
+
+				impl<'a> TryFrom<#alloc::borrow::Cow<'a, [u8]>> for #owned_ident {
+					type Error = #alloc::string::FromUtf8Error;
+
+					fn try_from(value: #alloc::borrow::Cow<'a, [u8]>) -> Result<Self, #alloc::string::FromUtf8Error> {
+						Self::from_bytes(value.into_owned())
+					}
+				}
+
+				impl<'a> TryFrom<&'a #alloc::vec::Vec<u8>> for #owned_ident {
+					type Error = ::core::str::Utf8Error;
+
+					fn try_from(value: &'a #alloc::vec::Vec<u8>) -> Result<Self, ::core::str::Utf8Error> {
+						::core::str::from_utf8(value).map(Self::new)
+					}
+				}
+
+				impl From<#alloc::string::String> for #owned_ident {
+					fn from(value: #alloc::string::String) -> Self {
+						Self::new(value)
+					}
+				}
+
+				impl From<#alloc::boxed::Box<str>> for #owned_ident {
+					fn from(value: #alloc::boxed::Box<str>) -> Self {
+						Self::new(value)
+					}
+				}
+
+				impl<'a> From<#alloc::borrow::Cow<'a, str>> for #owned_ident {
+					fn from(value: #alloc::borrow::Cow<'a, str>) -> Self {
+						Self::new(value)
+					}
+				}
+
+				impl<'a> From<&'a str> for #owned_ident {
+					fn from(value: &'a str) -> Self {
+						Self::new(value)
+					}
+				}
+
+				impl<'a> Extend<&'a str> for #owned_ident {
+					fn extend<I: IntoIterator<Item = &'a str>>(&mut self, iter: I) {
+						self.mutate(|buffer| buffer.extend(iter))
+					}
+				}
+
+				impl Extend<#alloc::string::String> for #owned_ident {
+					fn extend<I: IntoIterator<Item = #alloc::string::String>>(&mut self, iter: I) {
+						self.mutate(|buffer| buffer.extend(iter))
+					}
+				}
+
+				impl Extend<char> for #owned_ident {
+					fn extend<I: IntoIterator<Item = char>>(&mut self, iter: I) {
+						self.mutate(|buffer| buffer.extend(iter))
+					}
+				}
+
+				impl ::core::str::FromStr for #owned_ident {
+					type Err = ::core::convert::Infallible;
+
+					fn from_str(value: &str) -> Result<Self, ::core::convert::Infallible> {
+						Ok(Self::new(value))
+					}
+				}
+			}
+		}
+	};
+
+	let serialize = foreign.serde.as_ref().map(|serde| {
+		let serialize = serialize_str(serde);
+
+		quote! {
+			impl ::serde::Serialize for #owned_ident {
+				fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+				where
+					S: ::serde::ser::Serializer
+				{
+					#serialize
+				}
+			}
+		}
+	});
+
+	let deserialize = foreign.serde.as_ref().map(|serde| {
+		let deserialize = deserialize_str(serde, false);
+
+		if error.is_some() {
+			quote! {
+				impl<'de> ::serde::Deserialize<'de> for #owned_ident {
+					fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+					where
+						D: ::serde::de::Deserializer<'de>
+					{
+						let s: #alloc::string::String = #deserialize;
+						#owned_ident::new(s).map_err(::serde::de::Error::custom)
+					}
+				}
+			}
+		} else {
+			quote! {
+				impl<'de> ::serde::Deserialize<'de> for #owned_ident {
+					fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+					where
+						D: ::serde::de::Deserializer<'de>
+					{
+						let s: #alloc::string::String = #deserialize;
+						Ok(Self::new(s))
+					}
+				}
+			}
+		}
+	});
+
+	let deserialize_cow = foreign.serde.as_ref().map(|serde| {
+		let cow = quote!(#alloc::borrow::Cow);
+		let (borrowed_str, owned_str) = if error.is_some() {
+			(
+				quote!(#ident::from_str(v).map(#cow::Borrowed).map_err(E::custom)),
+				quote!(#owned_ident::from_string(v).map(#cow::Owned).map_err(E::custom)),
+			)
+		} else {
+			(
+				quote!(Ok(#cow::Borrowed(#ident::from_str(v)))),
+				quote!(Ok(#cow::Owned(#owned_ident::from_string(v)))),
+			)
+		};
+
+		let (visit_bytes, deserialize) = if serde.human_readable {
+			(
+				Some(quote! {
+					fn visit_borrowed_bytes<E: ::serde::de::Error>(self, v: &'de [u8]) -> Result<Self::Value, E> {
+						self.visit_borrowed_str(::core::str::from_utf8(v).map_err(E::custom)?)
+					}
+
+					fn visit_bytes<E: ::serde::de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+						self.visit_str(::core::str::from_utf8(v).map_err(E::custom)?)
+					}
+
+					fn visit_byte_buf<E: ::serde::de::Error>(self, v: #alloc::vec::Vec<u8>) -> Result<Self::Value, E> {
+						self.visit_string(#alloc::string::String::from_utf8(v).map_err(E::custom)?)
+					}
+				}),
+				quote! {
+					if ::serde::de::Deserializer::is_human_readable(&deserializer) {
+						deserializer.deserialize_str(CowVisitor(::core::marker::PhantomData))
+					} else {
+						deserializer.deserialize_bytes(CowVisitor(::core::marker::PhantomData))
+					}
+				},
+			)
+		} else {
+			(
+				None,
+				quote!(deserializer.deserialize_str(CowVisitor(::core::marker::PhantomData))),
+			)
+		};
+
+		quote! {
+			impl #ident {
+				/// Deserializes a
+				#[doc = #name]
+				/// borrowing from the input when possible.
+				///
+				/// The value is only copied into an owned
+				#[doc = #name]
+				/// when the deserializer cannot lend the input (for instance
+				/// when it contains escape sequences). It can be used on
+				/// fields with the
+				/// `#[serde(borrow, deserialize_with = "...")]` attribute.
+				pub fn deserialize_cow<'a, 'de: 'a, D>(deserializer: D) -> Result<#cow<'a, Self>, D::Error>
+				where
+					D: ::serde::de::Deserializer<'de>
+				{
+					struct CowVisitor<'a>(::core::marker::PhantomData<&'a #ident>);
+
+					impl<'a, 'de: 'a> ::serde::de::Visitor<'de> for CowVisitor<'a> {
+						type Value = #cow<'a, #ident>;
+
+						fn expecting(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+							write!(f, "a ")?;
+							f.write_str(#name)
+						}
+
+						fn visit_borrowed_str<E: ::serde::de::Error>(self, v: &'de str) -> Result<Self::Value, E> {
+							#borrowed_str
+						}
+
+						fn visit_str<E: ::serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+							self.visit_string(#alloc::borrow::ToOwned::to_owned(v))
+						}
+
+						fn visit_string<E: ::serde::de::Error>(self, v: #alloc::string::String) -> Result<Self::Value, E> {
+							#owned_str
+						}
+
+						#visit_bytes
+					}
+
+					#deserialize
+				}
+			}
+		}
+	});
+
+	let schemars = foreign.schemars.as_ref().map(|_| {
+		quote! {
+			impl ::schemars::JsonSchema for #owned_ident {
+				fn schema_name() -> #alloc::borrow::Cow<'static, str> {
+					<#ident as ::schemars::JsonSchema>::schema_name()
+				}
+
+				fn schema_id() -> #alloc::borrow::Cow<'static, str> {
+					<#ident as ::schemars::JsonSchema>::schema_id()
+				}
+
+				fn json_schema(generator: &mut ::schemars::SchemaGenerator) -> ::schemars::Schema {
+					<#ident as ::schemars::JsonSchema>::json_schema(generator)
+				}
+			}
+		}
+	});
+
+	let proptest = options.proptest.as_ref().map(|proptest| {
+		let strings = match &proptest.regex {
+			Some(regex) => quote! {
+				str_newtype::proptest::string::string_regex(#regex).expect("invalid regular expression")
+			},
+			None => quote! {
+				str_newtype::proptest::arbitrary::any::<#alloc::string::String>()
+			},
+		};
+
+		let strategy = if error.is_some() {
+			let reason = format!("invalid {name}");
+			quote!(prop_filter_map(#reason, |s| Self::new(s).ok()))
+		} else {
+			quote!(prop_map(Self::new))
+		};
+
+		quote! {
+			impl #owned_ident {
+				/// Returns a strategy generating arbitrary owned
+				#[doc = #name]
+				/// values, for property testing.
+				pub fn arb() -> impl str_newtype::proptest::strategy::Strategy<Value = Self> {
+					use str_newtype::proptest::strategy::Strategy;
+					#strings.#strategy
+				}
+			}
+		}
+	});
+
+	let borsh = foreign.borsh.then(|| {
+		let from_string = if error.is_some() {
+			quote! {
+				Self::new(s).map_err(|e| {
+					str_newtype::borsh::io::Error::new(
+						str_newtype::borsh::io::ErrorKind::InvalidData,
+						#alloc::string::ToString::to_string(&e)
+					)
+				})
+			}
+		} else {
+			quote!(Ok(Self::new(s)))
+		};
+
+		quote! {
+			impl str_newtype::borsh::BorshSerialize for #owned_ident {
+				fn serialize<W: str_newtype::borsh::io::Write>(&self, writer: &mut W) -> str_newtype::borsh::io::Result<()> {
+					<str as str_newtype::borsh::BorshSerialize>::serialize(self.as_str(), writer)
+				}
+			}
+
+			impl str_newtype::borsh::BorshDeserialize for #owned_ident {
+				fn deserialize_reader<R: str_newtype::borsh::io::Read>(reader: &mut R) -> str_newtype::borsh::io::Result<Self> {
+					let s = <#alloc::string::String as str_newtype::borsh::BorshDeserialize>::deserialize_reader(reader)?;
+					#from_string
+				}
+			}
+		}
+	});
+
+	let diesel = foreign.diesel.then(|| {
+		let as_expression = diesel_as_expression(&quote!(#owned_ident), true);
+		let from_string = if error.is_some() {
+			quote!(Ok(Self::new(s)?))
+		} else {
+			quote!(Ok(Self::new(s)))
+		};
+
+		quote! {
+			impl<DB> ::diesel::serialize::ToSql<::diesel::sql_types::Text, DB> for #owned_ident
+			where
+				DB: ::diesel::backend::Backend,
+				str: ::diesel::serialize::ToSql<::diesel::sql_types::Text, DB>
+			{
+				fn to_sql<'b>(&'b self, out: &mut ::diesel::serialize::Output<'b, '_, DB>) -> ::diesel::serialize::Result {
+					<str as ::diesel::serialize::ToSql<::diesel::sql_types::Text, DB>>::to_sql(self.as_str(), out)
+				}
+			}
+
+			impl<DB> ::diesel::deserialize::FromSql<::diesel::sql_types::Text, DB> for #owned_ident
+			where
+				DB: ::diesel::backend::Backend,
+				#alloc::string::String: ::diesel::deserialize::FromSql<::diesel::sql_types::Text, DB>
+			{
+				fn from_sql(bytes: DB::RawValue<'_>) -> ::diesel::deserialize::Result<Self> {
+					let s = <#alloc::string::String as ::diesel::deserialize::FromSql<::diesel::sql_types::Text, DB>>::from_sql(bytes)?;
+					#from_string
+				}
+			}
+
+			impl<DB, ST> ::diesel::deserialize::Queryable<ST, DB> for #owned_ident
+			where
+				DB: ::diesel::backend::Backend,
+				ST: ::diesel::sql_types::SingleValue,
+				Self: ::diesel::deserialize::FromSql<ST, DB>
+			{
+				type Row = Self;
+
+				fn build(row: Self) -> ::diesel::deserialize::Result<Self> {
+					Ok(row)
+				}
+			}
+
+			#as_expression
+		}
+	});
+
+	let serde_json = foreign.serde_json.and(error).map(|error| {
+		let map_error = error_value(
+			options,
+			error,
+			quote!(::serde_json::Value::String(e.0)),
+			quote!(e.1),
+		);
+		let invalid = error_value(options, error, quote!(other), quote!(None));
+		quote! {
+			impl TryFrom<::serde_json::Value> for #owned_ident {
+				type Error = #error<::serde_json::Value>;
+
+				fn try_from(value: ::serde_json::Value) -> Result<Self, #error<::serde_json::Value>> {
+					match value {
+						::serde_json::Value::String(s) => {
+							Self::new(s).map_err(|e| #map_error)
+						}
+						other => Err(#invalid)
+					}
+				}
+			}
+		}
+	});
+
+	let os = foreign.os.then(|| {
+		let std = std_path();
+		let try_from_os = match error {
+			Some(error) => {
+				let map_error = map_error(options, error, quote!(value));
+				let invalid = error_value(options, error, quote!(value), quote!(None));
+				let map_string_error = error_value(
+					options,
+					error,
+					quote!(#std::ffi::OsString::from(e.0)),
+					quote!(e.1),
+				);
+				quote! {
+					impl<'a> TryFrom<&'a #std::ffi::OsStr> for #owned_ident {
+						type Error = #error<&'a #std::ffi::OsStr>;
+
+						fn try_from(value: &'a #std::ffi::OsStr) -> Result<Self, #error<&'a #std::ffi::OsStr>> {
+							match value.to_str() {
+								Some(s) => Self::try_from(s).map_err(#map_error),
+								None => Err(#invalid)
+							}
+						}
+					}
+
+					impl TryFrom<#std::ffi::OsString> for #owned_ident {
+						type Error = #error<#std::ffi::OsString>;
+
+						fn try_from(value: #std::ffi::OsString) -> Result<Self, #error<#std::ffi::OsString>> {
+							match value.into_string() {
+								Ok(s) => Self::new(s).map_err(|e| #map_string_error),
+								Err(value) => Err(#invalid)
+							}
+						}
+					}
+				}
+			}
+			None => quote! {
+				impl<'a> TryFrom<&'a #std::ffi::OsStr> for #owned_ident {
+					type Error = &'a #std::ffi::OsStr;
+
+					fn try_from(value: &'a #std::ffi::OsStr) -> Result<Self, &'a #std::ffi::OsStr> {
+						value.to_str().map(Self::from).ok_or(value)
+					}
+				}
+
+				impl TryFrom<#std::ffi::OsString> for #owned_ident {
+					type Error = #std::ffi::OsString;
+
+					fn try_from(value: #std::ffi::OsString) -> Result<Self, #std::ffi::OsString> {
+						value.into_string().map(Self::from)
+					}
+				}
+			},
+		};
+
+		cfg_std(quote! {
+			#try_from_os
+
+			impl AsRef<#std::ffi::OsStr> for #owned_ident {
+				fn as_ref(&self) -> &#std::ffi::OsStr {
+					self.as_str().as_ref()
+				}
+			}
+
+			impl From<#owned_ident> for #std::ffi::OsString {
+				fn from(value: #owned_ident) -> Self {
+					value.into_string().into()
+				}
+			}
+		})
+	});
+
+	let vis = error.is_none().then(|| quote! { pub });
+
+	let cow_alias = owned.cow.as_ref().map(|cow| {
+		quote! {
+			/// Copy-on-write
+			#[doc = concat!(#name, ".")]
+			pub type #cow<'a> = #alloc::borrow::Cow<'a, #ident>;
+		}
+	});
+
+	quote! {
+		/// Owned
+		#[doc = concat!(#name, ".")]
+		#[repr(transparent)]
+		pub struct #owned_ident(#vis #alloc::string::String);
+
+		const _: () = assert!(
+			::core::mem::size_of::<#owned_ident>() == ::core::mem::size_of::<#alloc::string::String>()
+				&& ::core::mem::align_of::<#owned_ident>() == ::core::mem::align_of::<#alloc::string::String>()
+		);
+
+		#constructor
+
+		impl ::core::clone::Clone for #owned_ident {
+			#[inline]
+			fn clone(&self) -> Self {
+				Self(self.0.clone())
+			}
+
+			/// Reuses the allocated capacity of `self` when possible.
+			#[inline]
+			fn clone_from(&mut self, source: &Self) {
+				self.0.clone_from(&source.0)
+			}
+		}
+
+		impl #owned_ident {
+			/// Creates a new owned
+			#[doc = #name]
+			/// from the input value without validation.
+			///
+			/// # Safety
+			/// The input value must be a valid
+			#[doc = concat!(#name, ".")]
+			pub unsafe fn new_unchecked(input: impl Into<#alloc::vec::Vec<u8>>) -> Self {
+				Self(unsafe {
+					#alloc::string::String::from_utf8_unchecked(input.into())
+				})
+			}
+
+			/// Returns the
+			#[doc = #name]
+			/// as a string.
+			pub fn as_str(&self) -> &str {
+				self.0.as_str()
+			}
+
+			/// Returns the
+			#[doc = #name]
+			/// as a byte string.
+			pub fn as_bytes(&self) -> &[u8] {
+				self.0.as_bytes()
+			}
+
+			/// Returns a copy of the
+			#[doc = #name]
+			/// as a `String`.
+			///
+			/// This clones the inner string directly, and is hence cheaper than
+			/// `ToString::to_string` which goes through the `Display`
+			/// formatting machinery.
+			pub fn to_string_owned(&self) -> #alloc::string::String {
+				self.0.clone()
+			}
+
+			#[inline]
+			pub fn into_string(self) -> #alloc::string::String {
+				self.0
+			}
+
+			#[inline]
+			pub fn into_bytes(self) -> #alloc::vec::Vec<u8> {
+				self.0.into_bytes()
+			}
+		}
+
+		impl ::core::borrow::Borrow<#ident> for #owned_ident {
+			fn borrow(&self) -> &#ident {
+				self.#as_ref()
+			}
+		}
+
+		// This also provides `&Type: TryFrom<&OwnedType, Error = Infallible>`
+		// for generic code.
+		impl<'a> From<&'a #owned_ident> for &'a #ident {
+			#[inline]
+			fn from(value: &'a #owned_ident) -> Self {
+				value.#as_ref()
+			}
+		}
+
+		impl #alloc::borrow::ToOwned for #ident {
+			type Owned = #owned_ident;
+
+			/// Copies the value into a buffer of the exact needed capacity.
+			#[inline]
+			fn to_owned(&self) -> Self::Owned {
+				#owned_ident(#alloc::borrow::ToOwned::to_owned(self.as_str()))
+			}
+		}
+
+		impl From<&#ident> for #owned_ident {
+			#[inline]
+			fn from(value: &#ident) -> Self {
+				#alloc::borrow::ToOwned::to_owned(value)
+			}
+		}
+
+		impl #ident {
+			/// Returns a borrowed copy-on-write
+			#[doc = concat!(#name, ".")]
+			pub fn into_cow(&self) -> #alloc::borrow::Cow<'_, Self> {
+				#alloc::borrow::Cow::Borrowed(self)
+			}
+		}
+
+		impl #owned_ident {
+			/// Returns an owned copy-on-write
+			#[doc = concat!(#name, ".")]
+			pub fn into_cow(self) -> #alloc::borrow::Cow<'static, #ident> {
+				#alloc::borrow::Cow::Owned(self)
+			}
+		}
+
+		impl<'a> From<&'a #ident> for #alloc::borrow::Cow<'a, #ident> {
+			fn from(value: &'a #ident) -> Self {
+				#alloc::borrow::Cow::Borrowed(value)
+			}
+		}
+
+		impl From<#owned_ident> for #alloc::borrow::Cow<'_, #ident> {
+			fn from(value: #owned_ident) -> Self {
+				#alloc::borrow::Cow::Owned(value)
+			}
+		}
+
+		impl #ident {
+			/// Turns this boxed
+			#[doc = #name]
+			/// into an owned one, without reallocating.
+			pub fn into_owned(self: #alloc::boxed::Box<Self>) -> #owned_ident {
+				let s = unsafe { #alloc::boxed::Box::from_raw(#alloc::boxed::Box::into_raw(self) as *mut str) };
+				#owned_ident(s.into_string())
+			}
+		}
+
+		impl #owned_ident {
+			/// Turns this owned
+			#[doc = #name]
+			/// into a boxed one.
+			///
+			/// The allocation is reused, but is shrunk to fit the value if it
+			/// has excess capacity.
+			pub fn into_boxed(self) -> #alloc::boxed::Box<#ident> {
+				let s = self.0.into_boxed_str();
+				unsafe { #alloc::boxed::Box::from_raw(#alloc::boxed::Box::into_raw(s) as *mut #ident) }
+			}
+		}
+
+		impl From<#alloc::boxed::Box<#ident>> for #owned_ident {
+			fn from(value: #alloc::boxed::Box<#ident>) -> Self {
+				value.into_owned()
+			}
+		}
+
+		impl From<#owned_ident> for #alloc::boxed::Box<#ident> {
+			fn from(value: #owned_ident) -> Self {
+				value.into_boxed()
+			}
+		}
+
+		#cow_alias
+
+		impl ::core::ops::Deref for #owned_ident {
+			type Target = #ident;
+
+			fn deref(&self) -> &Self::Target {
+				self.#as_ref()
+			}
+		}
+
+		impl AsRef<#ident> for #owned_ident {
+			fn as_ref(&self) -> &#ident {
+				self.#as_ref()
+			}
+		}
+
+		impl AsRef<str> for #owned_ident {
+			fn as_ref(&self) -> &str {
+				self.as_str()
+			}
+		}
+
+		impl AsRef<[u8]> for #owned_ident {
+			fn as_ref(&self) -> &[u8] {
+				self.as_str().as_bytes()
+			}
+		}
+
+		impl ::core::fmt::Debug for #owned_ident {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				#fmt_value
+			}
+		}
+
+		impl ::core::fmt::Display for #owned_ident {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				#fmt_value
+			}
+		}
+
+		impl From<#owned_ident> for #alloc::string::String {
+			#[inline]
+			fn from(value: #owned_ident) -> Self {
+				value.into_string()
+			}
+		}
+
+		impl From<#owned_ident> for #alloc::vec::Vec<u8> {
+			#[inline]
+			fn from(value: #owned_ident) -> Self {
+				value.into_bytes()
+			}
+		}
+
+		impl From<#owned_ident> for #alloc::sync::Arc<str> {
+			fn from(value: #owned_ident) -> Self {
+				value.into_string().into()
+			}
+		}
+
+		impl From<#owned_ident> for #alloc::rc::Rc<str> {
+			fn from(value: #owned_ident) -> Self {
+				value.into_string().into()
+			}
+		}
+
+		impl Extend<#owned_ident> for #alloc::vec::Vec<#alloc::string::String> {
+			fn extend<I: IntoIterator<Item = #owned_ident>>(&mut self, iter: I) {
+				Extend::extend(self, iter.into_iter().map(#owned_ident::into_string))
+			}
+		}
+
+		impl FromIterator<#owned_ident> for #alloc::vec::Vec<#alloc::string::String> {
+			fn from_iter<I: IntoIterator<Item = #owned_ident>>(iter: I) -> Self {
+				iter.into_iter().map(#owned_ident::into_string).collect()
+			}
+		}
+
+		#serialize
+
+		#deserialize
+
+		#deserialize_cow
+
+		#schemars
+
+		#diesel
+
+		#borsh
+
+		#serde_json
+
+		#os
+
+		#proptest
+
+		#empty
+
+		#concat
+
+		#collections
+
+		#(#derives)*
+	}
+}
+
+/// Generates the `diesel` expression conversions of `ty` (by reference, and
+/// by value if `sized`) to nullable and non-nullable `Text` values, relying on
+/// `ty: ToSql<Text, DB>`.
+fn diesel_as_expression(ty: &TokenStream, sized: bool) -> TokenStream {
+	let text = quote!(::diesel::sql_types::Text);
+	let bound = quote!(::diesel::internal::derives::as_expression::Bound);
+
+	let impls = [text.clone(), quote!(::diesel::sql_types::Nullable<#text>)]
+		.into_iter()
+		.map(|sql_type| {
+			let by_value = sized.then(|| {
+				quote! {
+					impl ::diesel::expression::AsExpression<#sql_type> for #ty {
+						type Expression = #bound<#sql_type, Self>;
+
+						fn as_expression(self) -> Self::Expression {
+							#bound::new(self)
+						}
+					}
+				}
+			});
+
+			quote! {
+				impl<'a> ::diesel::expression::AsExpression<#sql_type> for &'a #ty {
+					type Expression = #bound<#sql_type, Self>;
+
+					fn as_expression(self) -> Self::Expression {
+						#bound::new(self)
+					}
+				}
+
+				#by_value
+			}
+		});
+
+	quote! {
+		#(#impls)*
+
+		impl<DB> ::diesel::serialize::ToSql<::diesel::sql_types::Nullable<#text>, DB> for #ty
+		where
+			DB: ::diesel::backend::Backend,
+			Self: ::diesel::serialize::ToSql<#text, DB>
+		{
+			fn to_sql<'b>(&'b self, out: &mut ::diesel::serialize::Output<'b, '_, DB>) -> ::diesel::serialize::Result {
+				<Self as ::diesel::serialize::ToSql<#text, DB>>::to_sql(self, out)
+			}
+		}
 	}
 }
 
-fn partial_ord_impl(ident: &syn::Ident, ty: &syn::Type, fallible: bool) -> TokenStream {
-	if fallible {
+/// Generates the serialization of `self` (providing `as_str` and `as_bytes`)
+/// with `serializer`.
+///
+/// With the `human_readable` option, the value is serialized as bytes for
+/// non human-readable formats.
+fn serialize_str(serde: &SerdeOptions) -> TokenStream {
+	if serde.human_readable {
 		quote! {
-			impl PartialOrd<#ty> for #ident {
-				fn partial_cmp(&self, other: &#ty) -> Option<::core::cmp::Ordering> {
-					match Self::new(other) {
-						Ok(other) => self.partial_cmp(other),
-						Err(_) => None
-					}
-				}
-			}
-
-			impl PartialOrd<#ident> for #ty {
-				fn partial_cmp(&self, other: &#ident) -> Option<::core::cmp::Ordering> {
-					match #ident::new(self) {
-						Ok(this) => this.partial_cmp(other),
-						Err(_) => None
-					}
-				}
+			if serializer.is_human_readable() {
+				serializer.serialize_str(self.as_str())
+			} else {
+				serializer.serialize_bytes(self.as_bytes())
 			}
 		}
 	} else {
 		quote! {
-			impl PartialOrd<#ty> for #ident {
-				fn partial_cmp(&self, other: &#ty) -> Option<::core::cmp::Ordering> {
-					self.partial_cmp(Self::new(other))
-				}
-			}
-
-			impl PartialOrd<#ident> for #ty {
-				fn partial_cmp(&self, other: &#ident) -> Option<::core::cmp::Ordering> {
-					#ident::new(self).partial_cmp(other)
-				}
-			}
+			<str as ::serde::Serialize>::serialize(self.as_str(), serializer)
 		}
 	}
 }
 
-fn derive_owned_type(
-	name: &str,
-	ident: &syn::Ident,
-	options: &OwnedTypeOptions,
-	foreign: &ForeignOptions,
-	error: Option<&syn::Ident>,
-) -> TokenStream {
-	let as_ref = format_ident!("as_{}", SnakeCase(&ident.to_string()));
-	let owned_ident = &options.ident;
+/// Generates an expression deserializing a string (`&'a str` if `borrowed`,
+/// `String` otherwise) from `deserializer`, returning early on error.
+///
+/// With the `human_readable` option, the string is deserialized from bytes
+/// for non human-readable formats.
+fn deserialize_str(serde: &SerdeOptions, borrowed: bool) -> TokenStream {
+	let alloc = alloc_path();
 
-	let derives = options
-		.derives
-		.iter()
-		.map(|d| d.generate(ident, owned_ident, &as_ref, foreign));
+	let str = if borrowed {
+		quote! {
+			<&'a str as ::serde::Deserialize<'de>>::deserialize(deserializer)?
+		}
+	} else {
+		quote! {
+			<#alloc::string::String as ::serde::Deserialize<'de>>::deserialize(deserializer)?
+		}
+	};
 
-	let constructor = match error {
-		Some(error) => quote! {
-			impl #owned_ident {
-				/// Creates a new owned
-				#[doc = #name]
-				/// by parsing the input value.
-				pub fn new<T: str_newtype::Buffer>(input: T) -> Result<Self, #error<T>> {
-					if #ident::validate_bytes(input.as_bytes()) {
-						Ok(unsafe {
-							Self::new_unchecked(input.into_bytes())
-						})
-					} else {
-						Err(#error(input))
-					}
-				}
+	if serde.human_readable {
+		let bytes = if borrowed {
+			quote! {
+				::core::str::from_utf8(<&'a [u8] as ::serde::Deserialize<'de>>::deserialize(deserializer)?)
+					.map_err(::serde::de::Error::custom)?
+			}
+		} else {
+			quote! {
+				{
+					struct BytesVisitor;
 
-				/// Creates a new owned
-				#[doc = #name]
-				/// by parsing the input bytes.
-				pub fn from_bytes(input: Vec<u8>) -> Result<Self, #error<Vec<u8>>> {
-					Self::new(input)
-				}
+					impl<'de> ::serde::de::Visitor<'de> for BytesVisitor {
+						type Value = #alloc::vec::Vec<u8>;
 
-				/// Creates a new owned
-				#[doc = #name]
-				/// by parsing the input string.
-				pub fn from_string(input: String) -> Result<Self, #error> {
-					Self::new(input)
-				}
+						fn expecting(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+							f.write_str("a byte string")
+						}
 
-				/// Creates a new owned
-				#[doc = #name]
-				/// from the input value without validation.
-				///
-				/// # Safety
-				/// The input value must be a valid
-				#[doc = concat!(#name, ".")]
-				pub unsafe fn new_unchecked(input: impl Into<Vec<u8>>) -> Self {
-					Self(unsafe {
-						String::from_utf8_unchecked(input.into())
-					})
-				}
+						fn visit_bytes<E: ::serde::de::Error>(self, v: &[u8]) -> Result<#alloc::vec::Vec<u8>, E> {
+							Ok(v.to_vec())
+						}
 
-				pub const fn #as_ref(&self) -> &#ident {
-					unsafe {
-						#ident::new_unchecked(self.0.as_str())
+						fn visit_byte_buf<E: ::serde::de::Error>(self, v: #alloc::vec::Vec<u8>) -> Result<#alloc::vec::Vec<u8>, E> {
+							Ok(v)
+						}
 					}
+
+					#alloc::string::String::from_utf8(deserializer.deserialize_byte_buf(BytesVisitor)?)
+						.map_err(::serde::de::Error::custom)?
 				}
 			}
+		};
 
-			impl TryFrom<Vec<u8>> for #owned_ident {
-				type Error = #error<Vec<u8>>;
-
-				fn try_from(value: Vec<u8>) -> Result<Self, #error<Vec<u8>>> {
-					Self::new(value)
-				}
+		quote! {
+			if ::serde::de::Deserializer::is_human_readable(&deserializer) {
+				#str
+			} else {
+				#bytes
 			}
+		}
+	} else {
+		str
+	}
+}
 
-			impl TryFrom<String> for #owned_ident {
-				type Error = #error;
+fn derive_staging_type(
+	name: &str,
+	staging: &syn::Ident,
+	owned: &OwnedTypeOptions,
+	error: Option<&syn::Ident>,
+	secret: bool,
+) -> TokenStream {
+	let alloc = alloc_path();
+	let owned_ident = &owned.ident;
 
-				fn try_from(value: String) -> Result<Self, #error> {
-					Self::new(value)
+	let debug = if secret {
+		let placeholder = format!("[REDACTED {staging}]");
+		quote! {
+			impl ::core::fmt::Debug for #staging {
+				fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+					f.write_str(#placeholder)
 				}
 			}
-
-			impl ::std::str::FromStr for #owned_ident {
-				type Err = #error;
-
-				fn from_str(value: &str) -> Result<Self, #error> {
-					Self::new(value.to_owned())
+		}
+	} else {
+		quote! {
+			impl ::core::fmt::Debug for #staging {
+				fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+					::core::fmt::Debug::fmt(&self.0, f)
 				}
 			}
+		}
+	};
+
+	let build = match error {
+		Some(error) => quote! {
+			/// Validates the built string, returning the resulting owned
+			#[doc = concat!(#name, ".")]
+			pub fn build(self) -> Result<#owned_ident, #error> {
+				#owned_ident::from_string(self.0)
+			}
 		},
 		None => quote! {
-			impl #owned_ident {
-				/// Creates a new owned
-				#[doc = #name]
-				/// by parsing the input value.
-				pub fn new(input: impl Into<String>) -> Self {
-					Self(input.into())
-				}
+			/// Returns the built owned
+			#[doc = concat!(#name, ".")]
+			pub fn build(self) -> #owned_ident {
+				#owned_ident::from_string(self.0)
+			}
+		},
+	};
 
-				/// Creates a new owned
-				#[doc = #name]
-				/// by parsing the input string.
-				pub fn from_string(input: String) -> Self {
-					Self(input)
-				}
+	quote! {
+		/// Unvalidated
+		#[doc = #name]
+		/// being built.
+		///
+		/// The inner string can be freely mutated (through `DerefMut`), and is
+		/// only validated once by the `build` method.
+		#[derive(Clone, Default)]
+		pub struct #staging(pub #alloc::string::String);
+
+		#debug
+
+		impl #staging {
+			/// Creates a new empty builder.
+			pub fn new() -> Self {
+				Self::default()
+			}
 
-				/// Creates a new owned
-				#[doc = #name]
-				/// by parsing the input bytes.
-				pub fn from_bytes(input: Vec<u8>) -> Result<Self, ::std::string::FromUtf8Error> {
-					Ok(Self::new(String::from_utf8(input)?))
-				}
+			#build
+		}
 
-				pub fn #as_ref(&self) -> &#ident {
-					#ident::new(self.0.as_str())
-				}
+		impl ::core::ops::Deref for #staging {
+			type Target = #alloc::string::String;
+
+			fn deref(&self) -> &#alloc::string::String {
+				&self.0
 			}
+		}
 
-			impl TryFrom<Vec<u8>> for #owned_ident {
-				type Error = ::std::string::FromUtf8Error;
+		impl ::core::ops::DerefMut for #staging {
+			fn deref_mut(&mut self) -> &mut #alloc::string::String {
+				&mut self.0
+			}
+		}
 
-				fn try_from(value: Vec<u8>) -> Result<Self, ::std::string::FromUtf8Error> {
-					Self::from_bytes(value)
-				}
+		impl ::core::fmt::Write for #staging {
+			fn write_str(&mut self, s: &str) -> ::core::fmt::Result {
+				self.0.push_str(s);
+				Ok(())
 			}
+		}
 
-			impl From<String> for #owned_ident {
-				fn from(value: String) -> Self {
-					Self(value)
-				}
+		impl From<#alloc::string::String> for #staging {
+			fn from(value: #alloc::string::String) -> Self {
+				Self(value)
 			}
+		}
 
-			impl ::std::str::FromStr for #owned_ident {
-				type Err = ::std::convert::Infallible;
+		impl From<&str> for #staging {
+			fn from(value: &str) -> Self {
+				Self(#alloc::borrow::ToOwned::to_owned(value))
+			}
+		}
 
-				fn from_str(value: &str) -> Result<Self, ::std::convert::Infallible> {
-					Ok(Self(value.to_owned()))
-				}
+		impl From<#owned_ident> for #staging {
+			fn from(value: #owned_ident) -> Self {
+				Self(value.into_string())
 			}
-		},
-	};
+		}
+	}
+}
 
-	let serialize = foreign.serde.then(|| {
+fn derive_list_type(
+	name: &str,
+	list: &ListTypeOptions,
+	owned: &OwnedTypeOptions,
+	serde: bool,
+) -> TokenStream {
+	let list_ident = &list.ident;
+	let alloc = alloc_path();
+	let owned_ident = &owned.ident;
+	let separator = &list.separator;
+
+	let derives = [
+		(Derive::PartialEq, quote!(PartialEq)),
+		(Derive::Eq, quote!(Eq)),
+		(Derive::Hash, quote!(Hash)),
+	]
+	.into_iter()
+	.filter_map(|(d, tokens)| owned.derives.contains(d).then_some(tokens));
+
+	let dedup = owned.derives.contains(Derive::PartialEq).then(|| {
 		quote! {
-			impl ::serde::Serialize for #owned_ident {
+			/// Removes consecutive repeated items.
+			pub fn dedup(&mut self) {
+				self.0.dedup()
+			}
+		}
+	});
+
+	let serde = serde.then(|| {
+		quote! {
+			impl ::serde::Serialize for #list_ident {
 				fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 				where
 					S: ::serde::ser::Serializer
 				{
-					<str as ::serde::Serialize>::serialize(self.as_str(), serializer)
+					serializer.collect_str(self)
 				}
 			}
-		}
-	});
 
-	let deserialize = foreign.serde.then(|| {
-		if error.is_some() {
-			quote! {
-				impl<'de> ::serde::Deserialize<'de> for #owned_ident {
-					fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-					where
-						D: ::serde::de::Deserializer<'de>
-					{
-						#owned_ident::new(<String as ::serde::Deserialize<'de>>::deserialize(deserializer)?)
-							.map_err(::serde::de::Error::custom)
-					}
-				}
-			}
-		} else {
-			quote! {
-				impl<'de> ::serde::Deserialize<'de> for #owned_ident {
-					fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-					where
-						D: ::serde::de::Deserializer<'de>
-					{
-						<String as ::serde::Deserialize<'de>>::deserialize(deserializer).map(Self)?
-					}
+			impl<'de> ::serde::Deserialize<'de> for #list_ident {
+				fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+				where
+					D: ::serde::de::Deserializer<'de>
+				{
+					<#alloc::string::String as ::serde::Deserialize<'de>>::deserialize(deserializer)?
+						.parse()
+						.map_err(::serde::de::Error::custom)
 				}
 			}
 		}
 	});
 
-	let vis = error.is_none().then(|| quote! { pub });
-
 	quote! {
-		/// Owned
-		#[doc = concat!(#name, ".")]
-		#[derive(Clone)]
-		pub struct #owned_ident(#vis String);
-
-		#constructor
-
-		impl #owned_ident {
-			/// Returns the
-			#[doc = #name]
-			/// as a string.
-			pub fn as_str(&self) -> &str {
-				self.0.as_str()
+		/// List of
+		#[doc = #name]
+		/// values, parsed from and formatted to a
+		#[doc = concat!("`", #separator, "`")]
+		/// separated string.
+		///
+		/// The empty string is parsed as an empty list.
+		#[derive(Clone, Default #(, #derives)*)]
+		pub struct #list_ident(pub #alloc::vec::Vec<#owned_ident>);
+
+		impl #list_ident {
+			/// Separator between the list items.
+			pub const SEPARATOR: &'static str = #separator;
+
+			/// Returns the list items.
+			pub fn into_vec(self) -> #alloc::vec::Vec<#owned_ident> {
+				self.0
 			}
 
-			/// Returns the
-			#[doc = #name]
-			/// as a byte string.
-			pub fn as_bytes(&self) -> &[u8] {
-				self.0.as_bytes()
-			}
+			#dedup
+		}
 
-			pub fn into_string(self) -> String {
-				self.0
-			}
+		impl ::core::ops::Deref for #list_ident {
+			type Target = [#owned_ident];
 
-			pub fn into_bytes(self) -> Vec<u8> {
-				self.0.into_bytes()
+			fn deref(&self) -> &[#owned_ident] {
+				&self.0
 			}
 		}
 
-		impl ::std::borrow::Borrow<#ident> for #owned_ident {
-			fn borrow(&self) -> &#ident {
-				self.#as_ref()
+		impl From<#alloc::vec::Vec<#owned_ident>> for #list_ident {
+			fn from(value: #alloc::vec::Vec<#owned_ident>) -> Self {
+				Self(value)
 			}
 		}
 
-		impl ::std::borrow::ToOwned for #ident {
-			type Owned = #owned_ident;
-
-			fn to_owned(&self) -> Self::Owned {
-				#owned_ident(self.as_str().to_owned())
+		impl FromIterator<#owned_ident> for #list_ident {
+			fn from_iter<I: IntoIterator<Item = #owned_ident>>(iter: I) -> Self {
+				Self(iter.into_iter().collect())
 			}
 		}
 
-		impl ::core::ops::Deref for #owned_ident {
-			type Target = #ident;
+		impl IntoIterator for #list_ident {
+			type Item = #owned_ident;
+			type IntoIter = #alloc::vec::IntoIter<#owned_ident>;
 
-			fn deref(&self) -> &Self::Target {
-				self.#as_ref()
+			fn into_iter(self) -> Self::IntoIter {
+				self.0.into_iter()
 			}
 		}
 
-		impl AsRef<#ident> for #owned_ident {
-			fn as_ref(&self) -> &#ident {
-				self.#as_ref()
-			}
-		}
+		impl<'a> IntoIterator for &'a #list_ident {
+			type Item = &'a #owned_ident;
+			type IntoIter = ::core::slice::Iter<'a, #owned_ident>;
 
-		impl AsRef<str> for #owned_ident {
-			fn as_ref(&self) -> &str {
-				self.as_str()
+			fn into_iter(self) -> Self::IntoIter {
+				self.0.iter()
 			}
 		}
 
-		impl AsRef<[u8]> for #owned_ident {
-			fn as_ref(&self) -> &[u8] {
-				self.as_str().as_bytes()
-			}
-		}
+		impl ::core::str::FromStr for #list_ident {
+			type Err = <#owned_ident as ::core::str::FromStr>::Err;
 
-		impl ::core::fmt::Debug for #owned_ident {
-			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-				<#ident as ::core::fmt::Debug>::fmt(
-					self.#as_ref(),
-					f
-				)
+			fn from_str(value: &str) -> Result<Self, Self::Err> {
+				if value.is_empty() {
+					return Ok(Self(#alloc::vec::Vec::new()));
+				}
+
+				value.split(Self::SEPARATOR).map(str::parse).collect()
 			}
 		}
 
-		impl ::core::fmt::Display for #owned_ident {
+		impl ::core::fmt::Display for #list_ident {
 			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
-				<#ident as ::core::fmt::Display>::fmt(
-					self.#as_ref(),
-					f
-				)
-			}
-		}
+				for (i, item) in self.0.iter().enumerate() {
+					if i > 0 {
+						f.write_str(Self::SEPARATOR)?;
+					}
 
-		impl From<#owned_ident> for String {
-			fn from(value: #owned_ident) -> Self {
-				value.into_string()
+					::core::fmt::Display::fmt(item, f)?;
+				}
+
+				Ok(())
 			}
 		}
 
-		impl From<#owned_ident> for Vec<u8> {
-			fn from(value: #owned_ident) -> Self {
-				value.into_bytes()
+		impl ::core::fmt::Debug for #list_ident {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				f.debug_list().entries(&self.0).finish()
 			}
 		}
 
-		#serialize
-
-		#deserialize
-
-		#(#derives)*
+		#serde
 	}
 }
 
@@ -772,7 +3305,133 @@ fn owned_partial_ord_impl(
 	}
 }
 
+/// Ownership form of a string new type value, used to generate the
+/// comparison impls between forms.
+#[derive(Clone, Copy)]
+enum Form {
+	Owned,
+	Box,
+	Arc,
+	Ref,
+}
+
+impl Form {
+	/// Pairs of distinct forms compared with each other, on top of the
+	/// comparisons between the owned type and the (referenced) borrowed type.
+	const CROSS: [(Self, Self); 5] = [
+		(Self::Owned, Self::Box),
+		(Self::Owned, Self::Arc),
+		(Self::Box, Self::Arc),
+		(Self::Box, Self::Ref),
+		(Self::Arc, Self::Ref),
+	];
+
+	fn ty(self, ident: &syn::Ident, owned_ident: &syn::Ident) -> TokenStream {
+		let alloc = alloc_path();
+
+		match self {
+			Self::Owned => quote!(#owned_ident),
+			Self::Box => quote!(#alloc::boxed::Box<#ident>),
+			Self::Arc => quote!(#alloc::sync::Arc<#ident>),
+			Self::Ref => quote!(&#ident),
+		}
+	}
+
+	/// Borrows `value` (a reference to this form) as the borrowed type.
+	fn borrow(self, value: TokenStream, as_ref: &syn::Ident) -> TokenStream {
+		match self {
+			Self::Owned => quote!(#value.#as_ref()),
+			Self::Box | Self::Arc => quote!(&**#value),
+			Self::Ref => quote!(*#value),
+		}
+	}
+}
+
+/// Generates the `PartialEq` or `PartialOrd` impls (in both directions)
+/// between every pair of [`Form::CROSS`].
+fn cross_form_impls(
+	ident: &syn::Ident,
+	owned_ident: &syn::Ident,
+	as_ref: &syn::Ident,
+	ord: bool,
+) -> TokenStream {
+	let (trait_, method, output) = if ord {
+		(
+			quote!(PartialOrd),
+			quote!(partial_cmp),
+			quote!(Option<::core::cmp::Ordering>),
+		)
+	} else {
+		(quote!(PartialEq), quote!(eq), quote!(bool))
+	};
+
+	Form::CROSS
+		.iter()
+		.flat_map(|&(a, b)| [(a, b), (b, a)])
+		.map(|(a, b)| {
+			let a_ty = a.ty(ident, owned_ident);
+			let b_ty = b.ty(ident, owned_ident);
+			let lhs = a.borrow(quote!(self), as_ref);
+			let rhs = b.borrow(quote!(other), as_ref);
+
+			quote! {
+				impl #trait_<#b_ty> for #a_ty {
+					fn #method(&self, other: &#b_ty) -> #output {
+						<#ident as #trait_>::#method(#lhs, #rhs)
+					}
+				}
+			}
+		})
+		.collect()
+}
+
 impl Derive {
+	/// Generates the implementation of this trait for the borrowed type,
+	/// from the top-level `derive` sub-attribute, delegating to the inner
+	/// `str` (or `[u8]`).
+	fn generate_borrowed(&self, ident: &syn::Ident, derives: &Derives) -> TokenStream {
+		match self {
+			Self::PartialEq => quote! {
+				impl PartialEq for #ident {
+					#[inline]
+					fn eq(&self, other: &Self) -> bool {
+						self.0 == other.0
+					}
+				}
+			},
+			Self::Eq => quote! {
+				impl Eq for #ident {}
+			},
+			Self::PartialOrd => {
+				let body = if derives.contains(Self::Ord) {
+					quote!(Some(Ord::cmp(self, other)))
+				} else {
+					quote!(PartialOrd::partial_cmp(&self.0, &other.0))
+				};
+
+				quote! {
+					impl PartialOrd for #ident {
+						#[inline]
+						fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
+							#body
+						}
+					}
+				}
+			}
+			Self::Ord => quote! {
+				impl Ord for #ident {
+					#[inline]
+					fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+						Ord::cmp(&self.0, &other.0)
+					}
+				}
+			},
+			// `Default` is rejected and `Hash` is generated by the `hash`
+			// sub-attribute.
+			Self::Default | Self::Hash => TokenStream::new(),
+		}
+	}
+
 	fn generate(
 		&self,
 		ident: &syn::Ident,
@@ -782,20 +3441,24 @@ impl Derive {
 	) -> TokenStream {
 		match self {
 			Self::Default => {
+				let alloc = alloc_path();
+
 				quote! {
 					impl ::core::default::Default for #owned_ident {
 						fn default() -> Self {
-							<&'static #ident as ::core::default::Default>::default().to_owned()
+							#alloc::borrow::ToOwned::to_owned(<&'static #ident as ::core::default::Default>::default())
 						}
 					}
 				}
 			}
 			Self::PartialEq => {
-				let foreign = foreign
-					.eq
-					.iter()
-					.chain(&foreign.ord)
-					.map(|ty| owned_partial_eq_impl(owned_ident, as_ref, ty));
+				let alloc = alloc_path();
+				let foreign = builtin_eq_types()
+					.into_iter()
+					.chain([syn::parse_quote!(#alloc::string::String)])
+					.chain(foreign.eq_types().cloned())
+					.map(|ty| owned_partial_eq_impl(owned_ident, as_ref, &ty));
+				let cross = cross_form_impls(ident, owned_ident, as_ref, false);
 
 				quote! {
 					impl PartialEq for #owned_ident {
@@ -843,6 +3506,8 @@ impl Derive {
 						}
 					}
 
+					#cross
+
 					#(#foreign)*
 				}
 			}
@@ -856,6 +3521,7 @@ impl Derive {
 					.ord
 					.iter()
 					.map(|ty| owned_partial_ord_impl(owned_ident, as_ref, ty));
+				let cross = cross_form_impls(ident, owned_ident, as_ref, true);
 
 				quote! {
 					impl PartialOrd for #owned_ident {
@@ -903,6 +3569,8 @@ impl Derive {
 						}
 					}
 
+					#cross
+
 					#(#foreign)*
 				}
 			}
@@ -922,10 +3590,7 @@ impl Derive {
 				quote! {
 					impl ::core::hash::Hash for #owned_ident {
 						fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
-							<#ident as ::core::hash::Hash>::hash(
-								self.#as_ref(),
-								state
-							)
+							<str as ::core::hash::Hash>::hash(self.as_str(), state)
 						}
 					}
 				}
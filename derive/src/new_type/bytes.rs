@@ -0,0 +1,741 @@
+//! Generation of `[u8]` new-types.
+//!
+//! Byte new-types share the validation machinery of `str` new-types, but
+//! skip the UTF-8 specific items (`as_str`, `Display`, `FromStr`, etc.).
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use super::{Input, error_impls, error_value, map_error, validate, validate_len};
+use crate::{
+	options::{Derive, Options, OwnedTypeOptions},
+	utils::{SnakeCase, alloc_path, cfg_alloc, cfg_alloc_else, require_alloc},
+};
+
+/// Returns the first sub-attribute set in `options` that is not supported
+/// on `[u8]` new-types, if any.
+pub fn unsupported_option(options: &Options) -> Option<&'static str> {
+	let foreign = &options.foreign;
+	let owned = options.owned.as_ref();
+
+	[
+		(!foreign.eq.is_empty(), "eq"),
+		(!foreign.ord.is_empty(), "ord"),
+		(foreign.serde.is_some(), "serde"),
+		(foreign.schemars.is_some(), "schemars"),
+		(foreign.os, "os"),
+		(foreign.diesel, "diesel"),
+		(foreign.borsh, "borsh"),
+		(foreign.serde_json.is_some(), "serde_json"),
+		(options.char_table, "char_table"),
+		(options.normalize, "normalize"),
+		(options.trim, "trim"),
+		(options.ascii.is_some(), "ascii"),
+		(options.case_insensitive, "case_insensitive"),
+		(options.concat.is_some(), "concat"),
+		(options.collections.is_some(), "collections"),
+		(options.secret, "secret"),
+		(options.gen_tests, "gen_tests"),
+		(options.list.is_some(), "list"),
+		(options.staging.is_some(), "staging"),
+		(options.rc.is_some(), "rc"),
+		(options.empty, "empty"),
+		(options.error_default.is_some(), "error_default"),
+		(options.proptest.is_some(), "proptest"),
+		(!options.subtypes.is_empty(), "subtype_of"),
+		(options.deref.is_some(), "deref"),
+		(
+			options.hash.as_ref().is_some_and(|h| h.fxhash),
+			"hash(fxhash)",
+		),
+		(owned.is_some_and(|o| o.cow.is_some()), "cow"),
+	]
+	.into_iter()
+	.find_map(|(set, name)| set.then_some(name))
+}
+
+pub fn derive_with_options(ident: syn::Ident, options: &Options) -> TokenStream {
+	let alloc = alloc_path();
+	let error = (!options.infallible).then(|| format_ident!("Invalid{ident}"));
+
+	let debug_name = ident.to_string();
+	let name = options.name(&ident);
+
+	let new_method_link = format!("[`{ident}::new`]");
+
+	let deref = (!options.no_deref).then(|| {
+		quote! {
+			impl ::core::ops::Deref for #ident {
+				type Target = [u8];
+
+				fn deref(&self) -> &[u8] {
+					&self.0
+				}
+			}
+		}
+	});
+
+	let owned_type = options
+		.owned
+		.as_ref()
+		.map(|owned| derive_owned_type(&name, &ident, options, owned, error.as_ref()))
+		.map(|owned_type| {
+			require_alloc(
+				owned_type,
+				"owned types require the `alloc` feature of `str-newtype`",
+			)
+		});
+
+	let constructor = match &error {
+		Some(error) => {
+			let (reason_param, reason_arg, reason_field) = match &options.validate_result {
+				Some(ty) => (
+					Some(quote!(, E = #ty)),
+					Some(quote!(, E)),
+					Some(quote!(, pub Option<E>)),
+				),
+				None => (None, None, None),
+			};
+			let reason_bound = |bound: TokenStream| {
+				options
+					.validate_result
+					.as_ref()
+					.map(|_| quote!(, E: #bound))
+			};
+			let reason_debug = reason_bound(quote!(::core::fmt::Debug));
+			let reason_display = reason_bound(quote!(::core::fmt::Display));
+			let reason_error = reason_bound(quote!(::core::fmt::Debug + ::core::fmt::Display));
+
+			let (debug_reason, display_reason) = match &options.validate_result {
+				Some(_) => (
+					quote! {
+						write!(f, ", ")?;
+						self.1.fmt(f)?;
+					},
+					quote! {
+						if let Some(reason) = &self.1 {
+							write!(f, " ({reason})")?;
+						}
+					},
+				),
+				None => (quote!(), quote!()),
+			};
+
+			let err = |reason| {
+				let error = error_value(options, error, quote!(input), reason);
+				quote!(Err(#error))
+			};
+			let new = validate(
+				&ident,
+				options,
+				Input::Binary(quote!(bytes)),
+				&quote!(Ok(unsafe { Self::new_unchecked(bytes) })),
+				err,
+			);
+			let from_bytes = validate(
+				&ident,
+				options,
+				Input::Binary(quote!(input)),
+				&quote!(Ok(unsafe { Self::new_unchecked(input) })),
+				err,
+			);
+			let trait_impls = error_impls(options, error);
+			let static_panic_msg = format!("invalid `{ident}` literal");
+
+			let error_struct = |error_default: Option<TokenStream>| {
+				quote! {
+					/// Invalid
+					#[doc = #name]
+					/// error.
+					///
+					/// This error is raised by the
+					#[doc = #new_method_link]
+					/// when the input is not a valid
+					#[doc = concat!(#name, ".")]
+					pub struct #error<T #error_default #reason_param>(pub T #reason_field);
+				}
+			};
+			let error_struct = cfg_alloc_else(
+				error_struct(Some(quote!(= #alloc::vec::Vec<u8>))),
+				error_struct(None),
+			);
+
+			quote! {
+				#error_struct
+
+				impl<T: ::core::fmt::Debug #reason_debug> ::core::fmt::Debug for #error<T #reason_arg> {
+					fn fmt(&self, f: &mut core::fmt::Formatter) -> ::core::fmt::Result {
+						f.write_str(#debug_name)?;
+						write!(f, "(")?;
+						self.0.fmt(f)?;
+						#debug_reason
+						write!(f, ")")
+					}
+				}
+
+				impl<T: ::core::fmt::Debug #reason_display> ::core::fmt::Display for #error<T #reason_arg> {
+					fn fmt(&self, f: &mut core::fmt::Formatter) -> ::core::fmt::Result {
+						write!(f, "invalid ")?;
+						f.write_str(#name)?;
+						write!(f, ": {:?}", self.0)?;
+						#display_reason
+						Ok(())
+					}
+				}
+
+				impl<T: ::core::fmt::Debug #reason_error> ::core::error::Error for #error<T #reason_arg> {}
+
+				#trait_impls
+
+				impl #ident {
+					/// Creates a new
+					#[doc = #name]
+					/// by parsing the input value.
+					pub fn new<T: ?Sized + AsRef<[u8]>>(input: &T) -> Result<&Self, #error<&T>> {
+						let bytes = input.as_ref();
+						#new
+					}
+
+					/// Creates a new
+					#[doc = #name]
+					/// by parsing the input bytes.
+					pub const fn from_bytes(input: &[u8]) -> Result<&Self, #error<&[u8]>> {
+						#from_bytes
+					}
+
+					/// Creates a new
+					#[doc = #name]
+					/// from static bytes.
+					///
+					/// # Panics
+					///
+					/// Panics if the input is not a valid
+					#[doc = concat!(#name, ".")]
+					/// When evaluated in a `const` context (such as a `const` item
+					/// or block), this is a compile-time error.
+					pub const fn from_static(input: &'static [u8]) -> &'static Self {
+						match Self::from_bytes(input) {
+							Ok(value) => value,
+							Err(_) => panic!(#static_panic_msg)
+						}
+					}
+
+					/// Checks that the input bytes are a valid
+					#[doc = concat!(#name, ",")]
+					/// including the checks performed by the constructors on top
+					/// of `validate_bytes` (such as `len`).
+					pub const fn is_valid(input: &[u8]) -> bool {
+						Self::from_bytes(input).is_ok()
+					}
+				}
+
+				impl<'a> TryFrom<&'a [u8]> for &'a #ident {
+					type Error = #error<&'a [u8]>;
+
+					fn try_from(value: &'a [u8]) -> Result<&'a #ident, #error<&'a [u8]>> {
+						#ident::from_bytes(value)
+					}
+				}
+			}
+		}
+		None => {
+			quote! {
+				impl #ident {
+					/// Creates a new
+					#[doc = #name]
+					/// from the input value.
+					pub fn new<T: ?Sized + AsRef<[u8]>>(input: &T) -> &Self {
+						Self::from_bytes(input.as_ref())
+					}
+
+					/// Creates a new
+					#[doc = #name]
+					/// from the input bytes.
+					pub const fn from_bytes(input: &[u8]) -> &Self {
+						unsafe { Self::new_unchecked(input) }
+					}
+				}
+
+				impl<'a> From<&'a [u8]> for &'a #ident {
+					fn from(value: &'a [u8]) -> &'a #ident {
+						#ident::from_bytes(value)
+					}
+				}
+			}
+		}
+	};
+
+	let validate_len = options.len.as_ref().map(|len| {
+		let validate_len = validate_len(&name, len);
+		quote! {
+			impl #ident {
+				#validate_len
+			}
+		}
+	});
+
+	let derives = options
+		.derives
+		.iter()
+		.map(|d| d.generate_borrowed(&ident, &options.derives));
+
+	let hash = options.hash.as_ref().filter(|h| h.std).map(|_| {
+		quote! {
+			impl ::core::hash::Hash for #ident {
+				fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+					<[u8] as ::core::hash::Hash>::hash(self.as_bytes(), state)
+				}
+			}
+		}
+	});
+
+	let vec_eq = cfg_alloc(quote! {
+		impl PartialEq<#alloc::vec::Vec<u8>> for #ident {
+			fn eq(&self, other: &#alloc::vec::Vec<u8>) -> bool {
+				self.as_bytes() == other.as_slice()
+			}
+		}
+
+		impl PartialEq<#ident> for #alloc::vec::Vec<u8> {
+			fn eq(&self, other: &#ident) -> bool {
+				self.as_slice() == other.as_bytes()
+			}
+		}
+	});
+
+	quote! {
+		#constructor
+
+		#validate_len
+
+		impl #ident {
+			/// Creates a new
+			#[doc = #name]
+			/// from the input bytes without validation.
+			///
+			/// # Safety
+			/// The input bytes must be a valid
+			#[doc = concat!(#name, ".")]
+			pub const unsafe fn new_unchecked(input: &[u8]) -> &Self {
+				unsafe { ::core::mem::transmute::<&[u8], &Self>(input) }
+			}
+
+			/// Returns the
+			#[doc = #name]
+			/// as a byte string.
+			pub const fn as_bytes(&self) -> &[u8] {
+				&self.0
+			}
+
+			/// Returns the length of the
+			#[doc = #name]
+			/// in bytes.
+			pub const fn len(&self) -> usize {
+				self.0.len()
+			}
+
+			/// Checks if the
+			#[doc = #name]
+			/// is empty.
+			pub const fn is_empty(&self) -> bool {
+				self.0.is_empty()
+			}
+		}
+
+		/// Indexes the underlying bytes, returning bytes (and not a
+		#[doc = #name]
+		/// since arbitrary slices may not be valid).
+		impl<I: ::core::slice::SliceIndex<[u8]>> ::core::ops::Index<I> for #ident {
+			type Output = I::Output;
+
+			#[inline]
+			fn index(&self, index: I) -> &I::Output {
+				&self.0[index]
+			}
+		}
+
+		impl AsRef<#ident> for #ident {
+			fn as_ref(&self) -> &#ident {
+				self
+			}
+		}
+
+		impl AsRef<[u8]> for #ident {
+			fn as_ref(&self) -> &[u8] {
+				self.as_bytes()
+			}
+		}
+
+		impl ::core::borrow::Borrow<[u8]> for #ident {
+			fn borrow(&self) -> &[u8] {
+				self.as_bytes()
+			}
+		}
+
+		impl<'a> From<&'a #ident> for &'a [u8] {
+			fn from(value: &'a #ident) -> Self {
+				value.as_bytes()
+			}
+		}
+
+		impl ::core::fmt::Debug for #ident {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				f.write_str(#debug_name)?;
+				write!(f, "(")?;
+				::core::fmt::Debug::fmt(self.as_bytes(), f)?;
+				write!(f, ")")
+			}
+		}
+
+		impl ::core::fmt::LowerHex for #ident {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				str_newtype::__private::fmt_hex(self.as_bytes(), f, false)
+			}
+		}
+
+		impl ::core::fmt::UpperHex for #ident {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				str_newtype::__private::fmt_hex(self.as_bytes(), f, true)
+			}
+		}
+
+		impl PartialEq<[u8]> for #ident {
+			fn eq(&self, other: &[u8]) -> bool {
+				self.as_bytes() == other
+			}
+		}
+
+		impl PartialEq<#ident> for [u8] {
+			fn eq(&self, other: &#ident) -> bool {
+				self == other.as_bytes()
+			}
+		}
+
+		impl<'a> PartialEq<&'a [u8]> for #ident {
+			fn eq(&self, other: &&'a [u8]) -> bool {
+				self.as_bytes() == *other
+			}
+		}
+
+		impl<'a> PartialEq<#ident> for &'a [u8] {
+			fn eq(&self, other: &#ident) -> bool {
+				*self == other.as_bytes()
+			}
+		}
+
+		#vec_eq
+
+		#deref
+
+		#hash
+
+		#(#derives)*
+
+		#owned_type
+	}
+}
+
+fn derive_owned_type(
+	name: &str,
+	ident: &syn::Ident,
+	options: &Options,
+	owned: &OwnedTypeOptions,
+	error: Option<&syn::Ident>,
+) -> TokenStream {
+	let alloc = alloc_path();
+	let as_ref = owned
+		.as_ref
+		.clone()
+		.unwrap_or_else(|| format_ident!("as_{}", SnakeCase(&ident.to_string())));
+	let owned_ident = &owned.ident;
+
+	let derives = owned
+		.derives
+		.iter()
+		.map(|d| derive_owned_trait(d, ident, owned_ident, &as_ref));
+
+	let constructor = match error {
+		Some(error) => {
+			let new = validate(
+				ident,
+				options,
+				Input::Binary(quote!(&input)),
+				&quote!(Ok(Self(input))),
+				|reason| {
+					let error = error_value(options, error, quote!(input), reason);
+					quote!(Err(#error))
+				},
+			);
+			let try_from_array = validate(
+				ident,
+				options,
+				Input::Binary(quote!(&value)),
+				&quote!(Ok(Self(value.to_vec()))),
+				|reason| {
+					let error = error_value(options, error, quote!(value), reason);
+					quote!(Err(#error))
+				},
+			);
+			let map_error = map_error(options, error, quote!(value));
+
+			quote! {
+				impl #owned_ident {
+					/// Creates a new owned
+					#[doc = #name]
+					/// by parsing the input bytes.
+					pub fn new(input: #alloc::vec::Vec<u8>) -> Result<Self, #error> {
+						#new
+					}
+
+					/// Creates a new owned
+					#[doc = #name]
+					/// from the input bytes without validation.
+					///
+					/// # Safety
+					/// The input bytes must be a valid
+					#[doc = concat!(#name, ".")]
+					pub unsafe fn new_unchecked(input: #alloc::vec::Vec<u8>) -> Self {
+						Self(input)
+					}
+
+					pub const fn #as_ref(&self) -> &#ident {
+						unsafe { #ident::new_unchecked(self.0.as_slice()) }
+					}
+				}
+
+				impl TryFrom<#alloc::vec::Vec<u8>> for #owned_ident {
+					type Error = #error;
+
+					fn try_from(value: #alloc::vec::Vec<u8>) -> Result<Self, #error> {
+						Self::new(value)
+					}
+				}
+
+				impl<const N: usize> TryFrom<[u8; N]> for #owned_ident {
+					type Error = #error<[u8; N]>;
+
+					fn try_from(value: [u8; N]) -> Result<Self, #error<[u8; N]>> {
+						#try_from_array
+					}
+				}
+
+				impl<'a> TryFrom<&'a [u8]> for #owned_ident {
+					type Error = #error<&'a [u8]>;
+
+					fn try_from(value: &'a [u8]) -> Result<Self, #error<&'a [u8]>> {
+						#ident::from_bytes(value)
+							.map(#alloc::borrow::ToOwned::to_owned)
+							.map_err(#map_error)
+					}
+				}
+			}
+		}
+		None => {
+			quote! {
+				impl #owned_ident {
+					/// Creates a new owned
+					#[doc = #name]
+					/// from the input bytes.
+					pub fn new(input: impl Into<#alloc::vec::Vec<u8>>) -> Self {
+						Self(input.into())
+					}
+
+					pub const fn #as_ref(&self) -> &#ident {
+						#ident::from_bytes(self.0.as_slice())
+					}
+				}
+
+				impl From<#alloc::vec::Vec<u8>> for #owned_ident {
+					fn from(value: #alloc::vec::Vec<u8>) -> Self {
+						Self::new(value)
+					}
+				}
+
+				impl<'a> From<&'a [u8]> for #owned_ident {
+					fn from(value: &'a [u8]) -> Self {
+						Self::new(value)
+					}
+				}
+			}
+		}
+	};
+
+	let vis = error.is_none().then(|| quote! { pub });
+
+	quote! {
+		/// Owned
+		#[doc = concat!(#name, ".")]
+		#[repr(transparent)]
+		pub struct #owned_ident(#vis #alloc::vec::Vec<u8>);
+
+		#constructor
+
+		impl ::core::clone::Clone for #owned_ident {
+			#[inline]
+			fn clone(&self) -> Self {
+				Self(self.0.clone())
+			}
+
+			/// Reuses the allocated capacity of `self` when possible.
+			#[inline]
+			fn clone_from(&mut self, source: &Self) {
+				self.0.clone_from(&source.0)
+			}
+		}
+
+		impl #owned_ident {
+			/// Returns the
+			#[doc = #name]
+			/// as a byte string.
+			pub fn as_bytes(&self) -> &[u8] {
+				self.0.as_slice()
+			}
+
+			#[inline]
+			pub fn into_bytes(self) -> #alloc::vec::Vec<u8> {
+				self.0
+			}
+		}
+
+		impl ::core::borrow::Borrow<#ident> for #owned_ident {
+			fn borrow(&self) -> &#ident {
+				self.#as_ref()
+			}
+		}
+
+		// This also provides `&Type: TryFrom<&OwnedType, Error = Infallible>`
+		// for generic code.
+		impl<'a> From<&'a #owned_ident> for &'a #ident {
+			#[inline]
+			fn from(value: &'a #owned_ident) -> Self {
+				value.#as_ref()
+			}
+		}
+
+		impl #alloc::borrow::ToOwned for #ident {
+			type Owned = #owned_ident;
+
+			#[inline]
+			fn to_owned(&self) -> Self::Owned {
+				#owned_ident(self.as_bytes().to_vec())
+			}
+		}
+
+		impl From<&#ident> for #owned_ident {
+			#[inline]
+			fn from(value: &#ident) -> Self {
+				#alloc::borrow::ToOwned::to_owned(value)
+			}
+		}
+
+		impl ::core::ops::Deref for #owned_ident {
+			type Target = #ident;
+
+			fn deref(&self) -> &Self::Target {
+				self.#as_ref()
+			}
+		}
+
+		impl AsRef<#ident> for #owned_ident {
+			fn as_ref(&self) -> &#ident {
+				self.#as_ref()
+			}
+		}
+
+		impl AsRef<[u8]> for #owned_ident {
+			fn as_ref(&self) -> &[u8] {
+				self.as_bytes()
+			}
+		}
+
+		impl ::core::fmt::Debug for #owned_ident {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				::core::fmt::Debug::fmt(self.#as_ref(), f)
+			}
+		}
+
+		impl ::core::fmt::LowerHex for #owned_ident {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				::core::fmt::LowerHex::fmt(self.#as_ref(), f)
+			}
+		}
+
+		impl ::core::fmt::UpperHex for #owned_ident {
+			fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+				::core::fmt::UpperHex::fmt(self.#as_ref(), f)
+			}
+		}
+
+		impl From<#owned_ident> for #alloc::vec::Vec<u8> {
+			#[inline]
+			fn from(value: #owned_ident) -> Self {
+				value.into_bytes()
+			}
+		}
+
+		#(#derives)*
+	}
+}
+
+/// Generates the implementation of the given owned-type sub-attribute
+/// derive, delegating to the borrowed type.
+fn derive_owned_trait(
+	derive: Derive,
+	ident: &syn::Ident,
+	owned_ident: &syn::Ident,
+	as_ref: &syn::Ident,
+) -> TokenStream {
+	let alloc = alloc_path();
+
+	match derive {
+		Derive::Default => quote! {
+			impl ::core::default::Default for #owned_ident {
+				fn default() -> Self {
+					#alloc::borrow::ToOwned::to_owned(<&'static #ident as ::core::default::Default>::default())
+				}
+			}
+		},
+		Derive::PartialEq => quote! {
+			impl PartialEq for #owned_ident {
+				fn eq(&self, other: &Self) -> bool {
+					self.#as_ref() == other.#as_ref()
+				}
+			}
+
+			impl PartialEq<#ident> for #owned_ident {
+				fn eq(&self, other: &#ident) -> bool {
+					self.#as_ref() == other
+				}
+			}
+
+			impl PartialEq<#owned_ident> for #ident {
+				fn eq(&self, other: &#owned_ident) -> bool {
+					self == other.#as_ref()
+				}
+			}
+		},
+		Derive::Eq => quote! {
+			impl Eq for #owned_ident {}
+		},
+		Derive::PartialOrd => quote! {
+			impl PartialOrd for #owned_ident {
+				fn partial_cmp(&self, other: &Self) -> Option<::core::cmp::Ordering> {
+					self.#as_ref().partial_cmp(other.#as_ref())
+				}
+			}
+		},
+		Derive::Ord => quote! {
+			impl Ord for #owned_ident {
+				fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+					self.#as_ref().cmp(other.#as_ref())
+				}
+			}
+		},
+		Derive::Hash => quote! {
+			impl ::core::hash::Hash for #owned_ident {
+				fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+					<[u8] as ::core::hash::Hash>::hash(self.as_bytes(), state)
+				}
+			}
+		},
+	}
+}
@@ -1,6 +1,6 @@
 use crate::{
 	Error,
-	attribute::{Attribute, OwnedTypeAttribute},
+	attribute::{Attribute, ListTypeAttribute, OwnedTypeAttribute},
 };
 
 #[derive(Default)]
@@ -9,7 +9,29 @@ pub struct Options {
 	pub owned: Option<OwnedTypeOptions>,
 	pub foreign: ForeignOptions,
 	pub no_deref: bool,
+	pub deref: Option<syn::Path>,
+	pub derives: Derives,
 	pub infallible: bool,
+	pub char_table: bool,
+	pub normalize: bool,
+	pub trim: bool,
+	pub ascii: Option<proc_macro2::Span>,
+	pub case_insensitive: bool,
+	pub concat: Option<proc_macro2::Span>,
+	pub collections: Option<proc_macro2::Span>,
+	pub secret: bool,
+	pub gen_tests: bool,
+	pub list: Option<ListTypeOptions>,
+	pub hash: Option<HashOptions>,
+	pub len: Option<LenOptions>,
+	pub no_validate: bool,
+	pub validate_result: Option<syn::Type>,
+	pub rc: Option<proc_macro2::Span>,
+	pub staging: Option<syn::Ident>,
+	pub empty: bool,
+	pub error_default: Option<proc_macro2::Span>,
+	pub proptest: Option<ProptestOptions>,
+	pub subtypes: Vec<SubtypeOptions>,
 }
 
 impl Options {
@@ -19,7 +41,7 @@ impl Options {
 			.unwrap_or_else(|| ident.to_string().to_lowercase())
 	}
 
-	pub fn apply(&mut self, attr: Attribute) -> Result<(), Error> {
+	pub fn apply(&mut self, type_ident: &syn::Ident, attr: Attribute) -> Result<(), Error> {
 		match attr {
 			Attribute::Name(name) => match &mut self.name {
 				Some(n) => n.push_str(&name.value()),
@@ -27,11 +49,17 @@ impl Options {
 			},
 			Attribute::Owned(attrs) => {
 				let mut ident = None;
+				let mut suffix = None;
+				let mut as_ref = None;
+				let mut cow = None;
 				let mut derives = Derives::default();
 
 				for attr in attrs {
 					match attr {
 						OwnedTypeAttribute::Ident(i) => ident = Some(i),
+						OwnedTypeAttribute::AsRefName(i) => as_ref = Some(i),
+						OwnedTypeAttribute::Cow(i) => cow = Some(i),
+						OwnedTypeAttribute::Suffix(s) => suffix = Some(s.value()),
 						OwnedTypeAttribute::Derive(ds) => {
 							for d in ds {
 								derives.insert(d);
@@ -40,27 +68,159 @@ impl Options {
 					}
 				}
 
+				let ident = ident.or_else(|| {
+					suffix
+						.as_deref()
+						.map(|suffix| default_owned_ident(type_ident, Some(suffix)))
+				});
+
 				match &mut self.owned {
 					Some(sized) => {
 						if let Some(i) = ident {
 							sized.ident = i;
 						}
 
+						if let Some(i) = as_ref {
+							sized.as_ref = Some(i);
+						}
+
+						if let Some(i) = cow {
+							sized.cow = Some(i);
+						}
+
 						sized.derives.append(derives);
 					}
-					None => match ident {
-						Some(ident) => self.owned = Some(OwnedTypeOptions { ident, derives }),
-						None => {
-							todo!()
-						}
-					},
+					None => {
+						self.owned = Some(OwnedTypeOptions {
+							ident: ident.unwrap_or_else(|| default_owned_ident(type_ident, None)),
+							as_ref,
+							cow,
+							derives,
+						})
+					}
 				}
 			}
 			Attribute::Eq(types) => self.foreign.eq.extend(types),
 			Attribute::Ord(types) => self.foreign.ord.extend(types),
-			Attribute::Serde => self.foreign.serde = true,
+			Attribute::Serde(attrs) => {
+				let serde = self.foreign.serde.get_or_insert_with(SerdeOptions::default);
+				for attr in attrs {
+					match attr {
+						SerdeAttribute::HumanReadable => serde.human_readable = true,
+					}
+				}
+			}
+			Attribute::Schemars(attrs) => {
+				let schemars = self
+					.foreign
+					.schemars
+					.get_or_insert_with(SchemarsOptions::default);
+				for attr in attrs {
+					match attr {
+						SchemarsAttribute::Format(f) => schemars.format = Some(f.value()),
+						SchemarsAttribute::Pattern(p) => schemars.pattern = Some(p.value()),
+					}
+				}
+			}
+			Attribute::Proptest(span, attrs) => {
+				let proptest = self
+					.proptest
+					.get_or_insert(ProptestOptions { span, regex: None });
+				for attr in attrs {
+					match attr {
+						ProptestAttribute::Regex(r) => proptest.regex = Some(r.value()),
+					}
+				}
+			}
+			Attribute::Os => self.foreign.os = true,
+			Attribute::Diesel => self.foreign.diesel = true,
+			Attribute::Borsh => self.foreign.borsh = true,
+			Attribute::SerdeJson(span) => self.foreign.serde_json = Some(span),
 			Attribute::NoDeref => self.no_deref = true,
+			Attribute::Deref(ty) => self.deref = Some(ty),
+			Attribute::Derive(span, derives) => {
+				for d in derives {
+					match d {
+						Derive::Default => return Err(Error::BorrowedDefault(span)),
+						// Same as the `hash` sub-attribute.
+						Derive::Hash => {
+							self.hash.get_or_insert_with(HashOptions::default).std = true
+						}
+						d => self.derives.insert(d),
+					}
+				}
+			}
 			Attribute::Infallible => self.infallible = true,
+			Attribute::CharTable => self.char_table = true,
+			Attribute::Normalize => self.normalize = true,
+			Attribute::Trim => self.trim = true,
+			Attribute::Ascii(span) => self.ascii = Some(span),
+			Attribute::CaseInsensitive => self.case_insensitive = true,
+			Attribute::Concat(span) => self.concat = Some(span),
+			Attribute::Collections(span) => self.collections = Some(span),
+			Attribute::Secret => self.secret = true,
+			Attribute::GenTests => self.gen_tests = true,
+			Attribute::List(span, attrs) => {
+				let mut ident = None;
+				let mut separator = None;
+
+				for attr in attrs {
+					match attr {
+						ListTypeAttribute::Ident(i) => ident = Some(i),
+						ListTypeAttribute::Separator(s) => separator = Some(s.value()),
+					}
+				}
+
+				match &mut self.list {
+					Some(list) => {
+						if let Some(i) = ident {
+							list.ident = i;
+						}
+
+						if let Some(s) = separator {
+							list.separator = s;
+						}
+					}
+					None => match ident {
+						Some(ident) => {
+							self.list = Some(ListTypeOptions {
+								ident,
+								separator: separator.unwrap_or_else(|| ",".to_owned()),
+							})
+						}
+						None => return Err(Error::MissingListIdent(span)),
+					},
+				}
+			}
+			Attribute::Hash(attrs) => {
+				let hash = self.hash.get_or_insert_with(HashOptions::default);
+				match attrs {
+					Some(attrs) => {
+						for attr in attrs {
+							match attr {
+								HashAttribute::FxHash => hash.fxhash = true,
+							}
+						}
+					}
+					None => hash.std = true,
+				}
+			}
+			Attribute::ExactLen(len) => {
+				let n = len.base10_parse()?;
+				self.len = Some(LenOptions {
+					span: len.span(),
+					min: n,
+					max: Some(n),
+				})
+			}
+			Attribute::Len(len) => self.len = Some(len),
+			Attribute::NoValidate => self.no_validate = true,
+			Attribute::ValidateResult(ty) => self.validate_result = Some(ty),
+			Attribute::Rc(span) => self.rc = Some(span),
+			Attribute::Staging(ident) => self.staging = Some(ident),
+			Attribute::Empty => self.empty = true,
+			Attribute::ErrorDefault(span) => self.error_default = Some(span),
+			Attribute::SubtypeOf(ty, owned) => self.subtypes.push(SubtypeOptions { ty, owned }),
 		}
 
 		Ok(())
@@ -71,16 +231,139 @@ impl Options {
 pub struct ForeignOptions {
 	pub eq: Vec<syn::Type>,
 	pub ord: Vec<syn::Type>,
-	pub serde: bool,
+	pub serde: Option<SerdeOptions>,
+	pub schemars: Option<SchemarsOptions>,
+	pub os: bool,
+	pub diesel: bool,
+	pub borsh: bool,
+	pub serde_json: Option<proc_macro2::Span>,
+}
+
+/// Default owned type name, used when `owned` is given without identifier.
+///
+/// The trailing `Str` of the borrowed type name (if any) is replaced by the
+/// given suffix, which defaults to `String`, or `Buf` if the name does not
+/// end with `Str` (e.g. `IriStr` gives `IriString`, `Iri` gives `IriBuf`).
+fn default_owned_ident(type_ident: &syn::Ident, suffix: Option<&str>) -> syn::Ident {
+	let name = type_ident.to_string();
+	let ident = match name.strip_suffix("Str").filter(|base| !base.is_empty()) {
+		Some(base) => format!("{base}{}", suffix.unwrap_or("String")),
+		None => format!("{name}{}", suffix.unwrap_or("Buf")),
+	};
+
+	syn::Ident::new(&ident, type_ident.span())
+}
+
+impl ForeignOptions {
+	/// Types for which a `PartialEq` implementation must be generated, from
+	/// both the `eq` and `ord` sub-attributes.
+	///
+	/// Types that are always compared (`str`, `&str` and `String`) are
+	/// excluded to avoid conflicting implementations.
+	pub fn eq_types(&self) -> impl Iterator<Item = &syn::Type> {
+		self.eq
+			.iter()
+			.chain(&self.ord)
+			.filter(|ty| !is_builtin_eq(ty))
+	}
+}
+
+/// Checks if the given type is `str`, `&str` or `String`.
+fn is_builtin_eq(ty: &syn::Type) -> bool {
+	match ty {
+		syn::Type::Path(p) => {
+			p.qself.is_none()
+				&& (p.path.is_ident("str")
+					|| p.path.segments.last().is_some_and(|s| s.ident == "String"))
+		}
+		syn::Type::Reference(r) => {
+			r.mutability.is_none()
+				&& matches!(&*r.elem, syn::Type::Path(p) if p.qself.is_none() && p.path.is_ident("str"))
+		}
+		syn::Type::Paren(p) => is_builtin_eq(&p.elem),
+		syn::Type::Group(g) => is_builtin_eq(&g.elem),
+		_ => false,
+	}
+}
+
+#[derive(Default)]
+pub struct SerdeOptions {
+	pub human_readable: bool,
+}
+
+pub enum SerdeAttribute {
+	HumanReadable,
+}
+
+#[derive(Default)]
+pub struct SchemarsOptions {
+	pub format: Option<String>,
+	pub pattern: Option<String>,
+}
+
+pub enum SchemarsAttribute {
+	Format(syn::LitStr),
+	Pattern(syn::LitStr),
+}
+
+/// Byte length bounds given by the `len` or `exact_len` sub-attributes.
+pub struct LenOptions {
+	pub span: proc_macro2::Span,
+
+	/// Minimum length.
+	pub min: usize,
+
+	/// Maximum length (inclusive).
+	pub max: Option<usize>,
+}
+
+impl LenOptions {
+	/// Checks if the length is exact.
+	pub fn is_exact(&self) -> bool {
+		self.max == Some(self.min)
+	}
+}
+
+/// Wider type given by the `subtype_of` sub-attribute.
+pub struct SubtypeOptions {
+	pub ty: syn::Path,
+	pub owned: Option<syn::Path>,
+}
+
+pub struct ProptestOptions {
+	pub span: proc_macro2::Span,
+	pub regex: Option<String>,
+}
+
+pub enum ProptestAttribute {
+	Regex(syn::LitStr),
+}
+
+#[derive(Default)]
+pub struct HashOptions {
+	pub std: bool,
+	pub fxhash: bool,
+}
+
+pub enum HashAttribute {
+	FxHash,
+}
+
+pub struct ListTypeOptions {
+	pub ident: syn::Ident,
+	pub separator: String,
 }
 
 pub struct OwnedTypeOptions {
 	pub ident: syn::Ident,
+	pub as_ref: Option<syn::Ident>,
+	pub cow: Option<syn::Ident>,
 	pub derives: Derives,
 }
 
 macro_rules! derives {
 	($($field:ident: $variant:ident),*) => {
+		#[derive(Clone, Copy)]
 		pub enum Derive {
 			$($variant),*
 		}
@@ -99,6 +382,14 @@ macro_rules! derives {
 				}
 			}
 
+			pub fn contains(&self, d: Derive) -> bool {
+				match d {
+					$(
+						Derive::$variant => self.$field,
+					)*
+				}
+			}
+
 			pub fn append(&mut self, other: Self) {
 				$(
 					self.$field |= other.$field;
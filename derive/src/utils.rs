@@ -1,3 +1,5 @@
+use proc_macro2::TokenStream;
+use quote::quote;
 use std::fmt;
 
 pub struct SnakeCase<'a>(pub &'a str);
@@ -32,3 +34,50 @@ impl<'a> quote::IdentFragment for SnakeCase<'a> {
 		fmt::Display::fmt(self, f)
 	}
 }
+
+/// Path to the `alloc` crate in the generated code, re-exported by
+/// `str-newtype` so that the deriving crate does not need to declare it.
+pub fn alloc_path() -> TokenStream {
+	quote!(str_newtype::__private::alloc)
+}
+
+/// Path to the `std` crate in the generated code.
+pub fn std_path() -> TokenStream {
+	quote!(str_newtype::__private::std)
+}
+
+/// Wraps the given items so that they are only compiled when the `alloc`
+/// feature of `str-newtype` is enabled.
+///
+/// The features of the derive macro cannot be used for this, since Cargo
+/// unifies them across the whole build, whereas the features of
+/// `str-newtype` are checked in the deriving crate.
+pub fn cfg_alloc(items: TokenStream) -> TokenStream {
+	quote!(str_newtype::__private::cfg_alloc! { #items })
+}
+
+/// Selects the given items when the `alloc` feature of `str-newtype` is
+/// enabled, and the alternative items otherwise.
+pub fn cfg_alloc_else(items: TokenStream, alternative: TokenStream) -> TokenStream {
+	quote!(str_newtype::__private::cfg_alloc! { { #items } else { #alternative } })
+}
+
+/// Wraps the given items so that they are only compiled when the `alloc`
+/// feature of `str-newtype` is enabled, emitting the given error otherwise.
+pub fn require_alloc(items: TokenStream, message: &str) -> TokenStream {
+	cfg_alloc_else(items, quote!(::core::compile_error!(#message);))
+}
+
+/// Wraps the given items so that they are only compiled when the `std`
+/// feature of `str-newtype` is enabled.
+pub fn cfg_std(items: TokenStream) -> TokenStream {
+	quote!(str_newtype::__private::cfg_std! { #items })
+}
+
+/// Wraps the given items so that they are only compiled when the `std`
+/// feature of `str-newtype` is enabled, emitting the given error otherwise.
+pub fn require_std(items: TokenStream, message: &str) -> TokenStream {
+	quote!(
+		str_newtype::__private::cfg_std! { { #items } else { ::core::compile_error!(#message); } }
+	)
+}
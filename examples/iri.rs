@@ -11,6 +11,7 @@ mod automata {}
     ord(str, &str, String),
     owned(IriBuf, derive(PartialEq))
 )]
+#[repr(transparent)]
 pub struct Iri(str);
 
 fn main() {
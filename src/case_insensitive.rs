@@ -0,0 +1,92 @@
+//! Case-insensitive comparison wrapper.
+use core::{
+	cmp::Ordering,
+	fmt,
+	hash::{Hash, Hasher},
+};
+
+/// Case-insensitive string wrapper.
+///
+/// Equality, ordering and hashing are computed on the Unicode lowercase
+/// mapping of the wrapped string (`char::to_lowercase`), without allocation,
+/// so that all three agree with each other. In particular, values can be used
+/// as case-insensitive `HashMap` (or `BTreeMap`) keys.
+///
+/// This is the type returned by `Type::case_insensitive` with the
+/// `case_insensitive` sub-attribute of [`StrNewType`](crate::StrNewType), but
+/// it can wrap any `T: AsRef<str>`.
+///
+/// ```
+/// use str_newtype::CaseInsensitive;
+/// use std::collections::HashMap;
+///
+/// let mut map = HashMap::new();
+/// map.insert(CaseInsensitive("Content-Type"), 1);
+/// assert_eq!(map.get(&CaseInsensitive("content-type")), Some(&1));
+/// assert_eq!(CaseInsensitive("ÉTÉ"), CaseInsensitive("été"));
+/// ```
+#[derive(Clone, Copy, Default)]
+#[repr(transparent)]
+pub struct CaseInsensitive<T: ?Sized>(pub T);
+
+impl<T> CaseInsensitive<T> {
+	/// Returns the wrapped value.
+	pub fn into_inner(self) -> T {
+		self.0
+	}
+}
+
+impl<T: ?Sized + AsRef<str>> CaseInsensitive<T> {
+	/// Returns the lowercase characters of the wrapped string, on which the
+	/// comparisons are performed.
+	fn folded(&self) -> impl Iterator<Item = char> + '_ {
+		self.0.as_ref().chars().flat_map(char::to_lowercase)
+	}
+}
+
+impl<T: ?Sized + AsRef<str>, U: ?Sized + AsRef<str>> PartialEq<CaseInsensitive<U>>
+	for CaseInsensitive<T>
+{
+	fn eq(&self, other: &CaseInsensitive<U>) -> bool {
+		self.folded().eq(other.folded())
+	}
+}
+
+impl<T: ?Sized + AsRef<str>> Eq for CaseInsensitive<T> {}
+
+impl<T: ?Sized + AsRef<str>, U: ?Sized + AsRef<str>> PartialOrd<CaseInsensitive<U>>
+	for CaseInsensitive<T>
+{
+	fn partial_cmp(&self, other: &CaseInsensitive<U>) -> Option<Ordering> {
+		Some(self.folded().cmp(other.folded()))
+	}
+}
+
+impl<T: ?Sized + AsRef<str>> Ord for CaseInsensitive<T> {
+	fn cmp(&self, other: &Self) -> Ordering {
+		self.folded().cmp(other.folded())
+	}
+}
+
+impl<T: ?Sized + AsRef<str>> Hash for CaseInsensitive<T> {
+	fn hash<H: Hasher>(&self, state: &mut H) {
+		for c in self.folded() {
+			state.write_u32(c as u32)
+		}
+
+		// Same terminator as `str`, preventing prefix collisions.
+		state.write_u8(0xff)
+	}
+}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for CaseInsensitive<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
+
+impl<T: ?Sized + fmt::Display> fmt::Display for CaseInsensitive<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.0.fmt(f)
+	}
+}
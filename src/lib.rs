@@ -18,6 +18,7 @@
 //! /// An `str` that is equal to `"foo"`.
 //! #[derive(StrNewType)]
 //! #[newtype(owned(FooString))]
+//! #[repr(transparent)]
 //! pub struct FooStr(str);
 //!
 //! impl FooStr {
@@ -49,8 +50,150 @@
 //! And much more. See the the [`StrNewType`] documentation for a full
 //! specification of what items are derived and how it can be controlled with
 //! the `newtype` attribute.
+//!
+//! # Without the derive macro
+//!
+//! For ad-hoc cases where defining a new type is overkill, the generic
+//! [`Validated<T, V>`](Validated) wrapper can be used instead, where `V` is a
+//! [`Validator`] type. `Validated<str, V>` and `Validated<String, V>` then
+//! play the role of the borrowed and owned types.
+//!
+//! # `no_std` support
+//!
+//! This crate is `no_std` compatible by disabling the default `std` feature.
+//! The `alloc` feature is then required to derive owned types. Without
+//! `alloc`, only the borrowed type is derived. The generated code refers to
+//! the `alloc` and `std` crates through this crate, so that it depends on the
+//! features of `str-newtype` (as unified by Cargo) and the deriving crate does
+//! not need to declare `extern crate alloc`.
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+
 pub use str_newtype_derive::StrNewType;
 
+mod validated;
+pub use validated::{InvalidValue, Validated, Validator};
+
+mod case_insensitive;
+pub use case_insensitive::CaseInsensitive;
+
+/// Fast, non DoS-resistant hashing used by the `hash(fxhash)` sub-attribute.
+#[cfg(feature = "fxhash")]
+pub use rustc_hash;
+
+/// Property testing, used by the `proptest` sub-attribute.
+#[cfg(feature = "proptest")]
+pub use proptest;
+
+/// Binary serialization, used by the `borsh` sub-attribute.
+#[cfg(feature = "borsh")]
+pub use borsh;
+
+/// Items used by the generated code. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+	#[cfg(feature = "alloc")]
+	pub extern crate alloc;
+
+	#[cfg(feature = "std")]
+	pub extern crate std;
+
+	#[cfg(feature = "alloc")]
+	pub use crate::__cfg_alloc_enabled as cfg_alloc;
+
+	#[cfg(not(feature = "alloc"))]
+	pub use crate::__cfg_alloc_disabled as cfg_alloc;
+
+	#[cfg(feature = "std")]
+	pub use crate::__cfg_std_enabled as cfg_std;
+
+	#[cfg(not(feature = "std"))]
+	pub use crate::__cfg_std_disabled as cfg_std;
+
+	/// Formats the given bytes in hexadecimal, for the `LowerHex` and
+	/// `UpperHex` implementations of byte new-types.
+	///
+	/// Just like integers, the `0x` prefix is written with the `#` flag, and
+	/// the width, fill, alignment (right by default) and `0` flag are
+	/// respected.
+	pub fn fmt_hex(bytes: &[u8], f: &mut core::fmt::Formatter, upper: bool) -> core::fmt::Result {
+		use core::fmt::{Alignment, Write};
+
+		fn fill(f: &mut core::fmt::Formatter, c: char, n: usize) -> core::fmt::Result {
+			for _ in 0..n {
+				f.write_char(c)?;
+			}
+
+			Ok(())
+		}
+
+		let prefix = if f.alternate() { "0x" } else { "" };
+		let len = prefix.len() + 2 * bytes.len();
+		let padding = f.width().map_or(0, |width| width.saturating_sub(len));
+
+		let (pre, zeros, post) = if f.sign_aware_zero_pad() {
+			(0, padding, 0)
+		} else {
+			match f.align() {
+				Some(Alignment::Left) => (0, 0, padding),
+				Some(Alignment::Center) => (padding / 2, 0, padding - padding / 2),
+				Some(Alignment::Right) | None => (padding, 0, 0),
+			}
+		};
+
+		fill(f, f.fill(), pre)?;
+		f.write_str(prefix)?;
+		fill(f, '0', zeros)?;
+
+		for b in bytes {
+			if upper {
+				write!(f, "{b:02X}")?;
+			} else {
+				write!(f, "{b:02x}")?;
+			}
+		}
+
+		fill(f, f.fill(), post)
+	}
+}
+
+/// Expands to the given items, or to the first group of `{ .. } else { .. }`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cfg_alloc_enabled {
+	({ $($t:tt)* } else { $($e:tt)* }) => { $($t)* };
+	($($t:tt)*) => { $($t)* };
+}
+
+/// Expands to nothing, or to the second group of `{ .. } else { .. }`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cfg_alloc_disabled {
+	({ $($t:tt)* } else { $($e:tt)* }) => { $($e)* };
+	($($t:tt)*) => {};
+}
+
+/// Same as `__cfg_alloc_enabled`, for the `std` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cfg_std_enabled {
+	({ $($t:tt)* } else { $($e:tt)* }) => { $($t)* };
+	($($t:tt)*) => { $($t)* };
+}
+
+/// Same as `__cfg_alloc_disabled`, for the `std` feature.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __cfg_std_disabled {
+	({ $($t:tt)* } else { $($e:tt)* }) => { $($e)* };
+	($($t:tt)*) => {};
+}
+
 /// Trusted byte buffer type.
 ///
 /// # Safety
@@ -58,6 +201,7 @@ pub use str_newtype_derive::StrNewType;
 /// Any interior mutability in the buffer type must not affect the `as_bytes`
 /// and `into_bytes` methods. In other words, as long as `self` is borrowed
 /// immutably those functions must always return the same result.
+#[cfg(feature = "alloc")]
 pub unsafe trait Buffer: Sized {
 	/// Borrows the buffer bytes.
 	fn as_bytes(&self) -> &[u8];
@@ -66,6 +210,7 @@ pub unsafe trait Buffer: Sized {
 	fn into_bytes(self) -> Vec<u8>;
 }
 
+#[cfg(feature = "alloc")]
 unsafe impl Buffer for Vec<u8> {
 	fn as_bytes(&self) -> &[u8] {
 		self
@@ -76,6 +221,7 @@ unsafe impl Buffer for Vec<u8> {
 	}
 }
 
+#[cfg(feature = "alloc")]
 unsafe impl Buffer for String {
 	fn as_bytes(&self) -> &[u8] {
 		self.as_bytes()
@@ -85,3 +231,36 @@ unsafe impl Buffer for String {
 		self.into_bytes()
 	}
 }
+
+#[cfg(feature = "alloc")]
+unsafe impl Buffer for Box<str> {
+	fn as_bytes(&self) -> &[u8] {
+		str::as_bytes(self)
+	}
+
+	fn into_bytes(self) -> Vec<u8> {
+		self.into_boxed_bytes().into_vec()
+	}
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl Buffer for Cow<'_, str> {
+	fn as_bytes(&self) -> &[u8] {
+		str::as_bytes(self)
+	}
+
+	fn into_bytes(self) -> Vec<u8> {
+		self.into_owned().into_bytes()
+	}
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl Buffer for Cow<'_, [u8]> {
+	fn as_bytes(&self) -> &[u8] {
+		self
+	}
+
+	fn into_bytes(self) -> Vec<u8> {
+		self.into_owned()
+	}
+}
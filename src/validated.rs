@@ -0,0 +1,273 @@
+//! Generic validated string wrapper.
+use core::{borrow::Borrow, fmt, hash::Hash, marker::PhantomData, ops::Deref};
+
+#[cfg(feature = "alloc")]
+use alloc::{borrow::ToOwned, string::String};
+
+/// String validator, used by [`Validated`].
+pub trait Validator {
+	/// Name of the validated values, used in error messages.
+	const NAME: &'static str;
+
+	/// Checks that the input string is valid.
+	fn validate(input: &str) -> bool;
+}
+
+/// Invalid value error, returned by the [`Validated`] constructors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidValue<T> {
+	/// Rejected value.
+	pub value: T,
+
+	/// Name of the expected values ([`Validator::NAME`]).
+	pub expected: &'static str,
+}
+
+impl<T: fmt::Display> fmt::Display for InvalidValue<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "invalid {}: {}", self.expected, self.value)
+	}
+}
+
+impl<T: fmt::Debug + fmt::Display> core::error::Error for InvalidValue<T> {}
+
+/// String validated by `V`.
+///
+/// This is a lightweight alternative to the [`StrNewType`](crate::StrNewType)
+/// derive macro for ad-hoc cases, where `Validated<str, V>` plays the role of
+/// the borrowed type and `Validated<String, V>` the role of the owned type.
+///
+/// ```
+/// use str_newtype::{Validated, Validator};
+///
+/// pub struct NonEmpty;
+///
+/// impl Validator for NonEmpty {
+///   const NAME: &'static str = "non-empty string";
+///
+///   fn validate(input: &str) -> bool {
+///     !input.is_empty()
+///   }
+/// }
+///
+/// let a: &Validated<str, NonEmpty> = Validated::new_ref("foo").unwrap();
+/// let b: Validated<String, NonEmpty> = Validated::new("foo".to_owned()).unwrap();
+/// assert_eq!(a, b.as_validated_str());
+/// assert!(Validated::<str, NonEmpty>::new_ref("").is_err());
+/// ```
+///
+/// The validation guarantee only holds as long as the `AsRef<str>`
+/// implementation of `T` consistently returns the same string, which is the
+/// case for all the standard string types.
+#[repr(transparent)]
+pub struct Validated<T: ?Sized, V> {
+	validator: PhantomData<fn() -> V>,
+	value: T,
+}
+
+impl<T, V: Validator> Validated<T, V>
+where
+	T: AsRef<str>,
+{
+	/// Validates the input value.
+	pub fn new(value: T) -> Result<Self, InvalidValue<T>> {
+		if V::validate(value.as_ref()) {
+			Ok(Self {
+				validator: PhantomData,
+				value,
+			})
+		} else {
+			Err(InvalidValue {
+				value,
+				expected: V::NAME,
+			})
+		}
+	}
+}
+
+impl<T: ?Sized, V: Validator> Validated<T, V>
+where
+	T: AsRef<str>,
+{
+	/// Validates the input reference.
+	pub fn new_ref(value: &T) -> Result<&Self, InvalidValue<&T>> {
+		if V::validate(value.as_ref()) {
+			Ok(unsafe { Self::new_ref_unchecked(value) })
+		} else {
+			Err(InvalidValue {
+				value,
+				expected: V::NAME,
+			})
+		}
+	}
+}
+
+impl<T, V> Validated<T, V> {
+	/// Wraps the input value without validation.
+	///
+	/// # Safety
+	///
+	/// The input value must be valid according to `V`.
+	pub const unsafe fn new_unchecked(value: T) -> Self {
+		Self {
+			validator: PhantomData,
+			value,
+		}
+	}
+
+	/// Returns the inner value.
+	pub fn into_inner(self) -> T {
+		self.value
+	}
+}
+
+impl<T: ?Sized, V> Validated<T, V> {
+	/// Wraps the input reference without validation.
+	///
+	/// # Safety
+	///
+	/// The input value must be valid according to `V`.
+	pub const unsafe fn new_ref_unchecked(value: &T) -> &Self {
+		unsafe { &*(value as *const T as *const Self) }
+	}
+
+	/// Returns a reference to the inner value.
+	pub const fn as_inner(&self) -> &T {
+		&self.value
+	}
+}
+
+impl<V> Validated<str, V> {
+	/// Returns the validated string.
+	pub const fn as_str(&self) -> &str {
+		&self.value
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<V> Validated<String, V> {
+	/// Returns the validated string.
+	pub fn as_str(&self) -> &str {
+		self.value.as_str()
+	}
+
+	/// Borrows this value as a `Validated<str, V>`.
+	pub fn as_validated_str(&self) -> &Validated<str, V> {
+		unsafe { Validated::new_ref_unchecked(self.value.as_str()) }
+	}
+}
+
+impl<T: ?Sized, V> Deref for Validated<T, V> {
+	type Target = T;
+
+	fn deref(&self) -> &T {
+		&self.value
+	}
+}
+
+impl<T: ?Sized + AsRef<str>, V> AsRef<str> for Validated<T, V> {
+	fn as_ref(&self) -> &str {
+		self.value.as_ref()
+	}
+}
+
+impl<T: ?Sized, V> Borrow<T> for Validated<T, V> {
+	fn borrow(&self) -> &T {
+		&self.value
+	}
+}
+
+impl<'a, V: Validator> TryFrom<&'a str> for &'a Validated<str, V> {
+	type Error = InvalidValue<&'a str>;
+
+	fn try_from(value: &'a str) -> Result<Self, Self::Error> {
+		Validated::new_ref(value)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<V: Validator> TryFrom<String> for Validated<String, V> {
+	type Error = InvalidValue<String>;
+
+	fn try_from(value: String) -> Result<Self, Self::Error> {
+		Self::new(value)
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<V: Validator> core::str::FromStr for Validated<String, V> {
+	type Err = InvalidValue<String>;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		Self::new(s.to_owned())
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<V> ToOwned for Validated<str, V> {
+	type Owned = Validated<String, V>;
+
+	fn to_owned(&self) -> Self::Owned {
+		unsafe { Validated::new_unchecked(self.value.to_owned()) }
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<V> Borrow<Validated<str, V>> for Validated<String, V> {
+	fn borrow(&self) -> &Validated<str, V> {
+		self.as_validated_str()
+	}
+}
+
+#[cfg(feature = "alloc")]
+impl<V> From<Validated<String, V>> for String {
+	fn from(value: Validated<String, V>) -> Self {
+		value.value
+	}
+}
+
+impl<T: Clone, V> Clone for Validated<T, V> {
+	fn clone(&self) -> Self {
+		unsafe { Self::new_unchecked(self.value.clone()) }
+	}
+}
+
+impl<T: Copy, V> Copy for Validated<T, V> {}
+
+impl<T: ?Sized + fmt::Debug, V> fmt::Debug for Validated<T, V> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.value.fmt(f)
+	}
+}
+
+impl<T: ?Sized + fmt::Display, V> fmt::Display for Validated<T, V> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		self.value.fmt(f)
+	}
+}
+
+impl<T: ?Sized + PartialEq<U>, U: ?Sized, V> PartialEq<Validated<U, V>> for Validated<T, V> {
+	fn eq(&self, other: &Validated<U, V>) -> bool {
+		self.value == other.value
+	}
+}
+
+impl<T: ?Sized + Eq, V> Eq for Validated<T, V> {}
+
+impl<T: ?Sized + PartialOrd, V> PartialOrd for Validated<T, V> {
+	fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+		self.value.partial_cmp(&other.value)
+	}
+}
+
+impl<T: ?Sized + Ord, V> Ord for Validated<T, V> {
+	fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+		self.value.cmp(&other.value)
+	}
+}
+
+impl<T: ?Sized + Hash, V> Hash for Validated<T, V> {
+	fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+		self.value.hash(state)
+	}
+}
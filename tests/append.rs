@@ -0,0 +1,44 @@
+mod common;
+
+use str_newtype::StrNewType;
+
+/// Non-empty string of ASCII digits.
+#[derive(StrNewType)]
+#[newtype(owned(DigitsString))]
+#[repr(transparent)]
+pub struct DigitsStr(str);
+
+common::validate_with!(DigitsStr, common::non_empty_digits);
+
+#[test]
+fn accepted_push() {
+	let mut s = DigitsString::new("12".to_owned()).unwrap();
+	s.try_push_str("34").unwrap();
+	s.try_push('5').unwrap();
+	s.append_all(["6", "78"]).unwrap();
+	assert_eq!(s.as_str(), "12345678");
+}
+
+#[test]
+fn rejected_push_str_rolls_back() {
+	let mut s = DigitsString::new("12".to_owned()).unwrap();
+	let e = s.try_push_str("3a").unwrap_err();
+	assert_eq!(s.as_bytes(), b"12");
+	assert_eq!(e.0, "123a");
+}
+
+#[test]
+fn rejected_push_rolls_back() {
+	let mut s = DigitsString::new("12".to_owned()).unwrap();
+	let e = s.try_push('é').unwrap_err();
+	assert_eq!(s.as_bytes(), b"12");
+	assert_eq!(e.0, "12é");
+}
+
+#[test]
+fn rejected_append_all_rolls_back() {
+	let mut s = DigitsString::new("12".to_owned()).unwrap();
+	let e = s.append_all(["3", "x", "4"]).unwrap_err();
+	assert_eq!(s.as_bytes(), b"12");
+	assert_eq!(e.0, "123x4");
+}
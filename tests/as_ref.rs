@@ -0,0 +1,35 @@
+use std::sync::Arc;
+use str_newtype::StrNewType;
+
+/// Any string.
+#[derive(StrNewType)]
+#[newtype(infallible, rc, owned(AnyString))]
+#[repr(transparent)]
+pub struct AnyStr(str);
+
+fn as_str_and_bytes<T: AsRef<str> + AsRef<[u8]>>(value: T) -> (String, Vec<u8>) {
+	let s: &str = value.as_ref();
+	let bytes: &[u8] = value.as_ref();
+	(s.to_owned(), bytes.to_owned())
+}
+
+#[test]
+fn every_form() {
+	let expected = || ("foo".to_owned(), b"foo".to_vec());
+
+	let borrowed = AnyStr::new("foo");
+	assert_eq!(as_str_and_bytes(borrowed), expected());
+
+	let owned = borrowed.to_owned();
+	assert_eq!(as_str_and_bytes(&owned), expected());
+	assert_eq!(as_str_and_bytes(owned.clone()), expected());
+
+	let boxed: Box<AnyStr> = owned.into_boxed();
+	assert_eq!(as_str_and_bytes(&*boxed), expected());
+	assert_eq!(as_str_and_bytes(boxed), expected());
+
+	// `Arc<AnyStr>` cannot implement `AsRef<str>` (orphan rules), but
+	// dereferences to a type that does.
+	let arc: Arc<AnyStr> = borrowed.into();
+	assert_eq!(as_str_and_bytes(&*arc), expected());
+}
@@ -0,0 +1,70 @@
+mod common;
+
+use str_newtype::StrNewType;
+
+/// Non-empty string without whitespace.
+///
+/// The validation methods accept non-ASCII characters, which are rejected by
+/// the `ascii` sub-attribute.
+#[derive(StrNewType)]
+#[newtype(ascii, owned(TokenString))]
+#[repr(transparent)]
+pub struct TokenStr(str);
+
+common::validate_with!(TokenStr, common::token);
+
+/// Lowercase ASCII letters.
+#[derive(StrNewType)]
+#[newtype(ascii, owned(LowerString))]
+#[repr(transparent)]
+pub struct LowerStr(str);
+
+common::validate_with!(LowerStr, common::lowercase);
+
+#[test]
+fn non_ascii_is_rejected() {
+	assert!(TokenStr::new("caf").is_ok());
+	assert!(TokenStr::new("café").is_err());
+	assert!(TokenStr::from_bytes("ü".as_bytes()).is_err());
+	assert!(TokenString::new("naïve".to_owned()).is_err());
+	assert!(!TokenStr::is_valid("日本"));
+}
+
+#[test]
+fn eq_ignore_ascii_case() {
+	let t = TokenStr::new("Content-Type").unwrap();
+	assert!(t.eq_ignore_ascii_case("content-type"));
+	assert!(t.eq_ignore_ascii_case("CONTENT-TYPE"));
+	assert!(!t.eq_ignore_ascii_case("content-length"));
+}
+
+#[test]
+fn case_round_trip() {
+	let t = TokenStr::new("Content-Type").unwrap();
+
+	let lower = t.try_to_ascii_lowercase().unwrap();
+	assert_eq!(lower.as_str(), "content-type");
+
+	let upper = lower.try_to_ascii_uppercase().unwrap();
+	assert_eq!(upper.as_str(), "CONTENT-TYPE");
+
+	let lower = upper.try_to_ascii_lowercase().unwrap();
+	assert_eq!(lower.as_str(), "content-type");
+	assert!(lower.eq_ignore_ascii_case(t.as_str()));
+}
+
+#[test]
+fn case_change_is_validated() {
+	let l = LowerStr::new("abc").unwrap();
+	assert_eq!(l.try_to_ascii_lowercase().unwrap().as_str(), "abc");
+	assert_eq!(l.try_to_ascii_uppercase().unwrap_err().into_inner(), "ABC");
+}
+
+#[test]
+fn str_case_methods_are_not_shadowed() {
+	let t = TokenStr::new("Content-Type").unwrap();
+	let lower: String = t.to_ascii_lowercase();
+	let upper: String = t.to_ascii_uppercase();
+	assert_eq!(lower, "content-type");
+	assert_eq!(upper, "CONTENT-TYPE");
+}
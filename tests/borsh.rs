@@ -0,0 +1,44 @@
+#![cfg(feature = "borsh")]
+mod common;
+
+use str_newtype::{
+	StrNewType,
+	borsh::{self, io::ErrorKind},
+};
+
+/// ASCII digits.
+#[derive(StrNewType)]
+#[newtype(borsh, owned(DigitsString))]
+#[repr(transparent)]
+pub struct DigitsStr(str);
+
+common::validate_with!(DigitsStr, common::digits);
+
+#[test]
+fn round_trip() {
+	let bytes = borsh::to_vec(DigitsStr::new("0123").unwrap()).unwrap();
+	assert_eq!(bytes, borsh::to_vec("0123").unwrap());
+
+	let owned: DigitsString = borsh::from_slice(&bytes).unwrap();
+	assert_eq!(owned.as_str(), "0123");
+	assert_eq!(borsh::to_vec(&owned).unwrap(), bytes);
+}
+
+#[test]
+fn invalid_value_is_rejected() {
+	let bytes = borsh::to_vec("12a").unwrap();
+	let e = borsh::from_slice::<DigitsString>(&bytes).unwrap_err();
+	assert_eq!(e.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn malformed_stream_is_rejected() {
+	// Length prefix of 3 followed by invalid UTF-8.
+	let bytes = [3, 0, 0, 0, b'1', 0xff, b'2'];
+	let e = borsh::from_slice::<DigitsString>(&bytes).unwrap_err();
+	assert_eq!(e.kind(), ErrorKind::InvalidData);
+
+	// Truncated input.
+	let bytes = [3, 0, 0, 0, b'1'];
+	assert!(borsh::from_slice::<DigitsString>(&bytes).is_err());
+}
@@ -0,0 +1,35 @@
+mod common;
+
+use str_newtype::StrNewType;
+
+/// ASCII digits.
+#[derive(StrNewType)]
+#[newtype(owned(DigitsString))]
+#[repr(transparent)]
+pub struct DigitsStr(str);
+
+common::validate_with!(DigitsStr, common::digits);
+
+#[test]
+fn boxed_round_trip() {
+	// `to_owned` allocates exactly the needed capacity, so boxing does not
+	// need to shrink the allocation.
+	let owned = DigitsString::new("0123".to_owned()).unwrap();
+	let ptr = owned.as_str().as_ptr();
+
+	let boxed: Box<DigitsStr> = owned.into_boxed();
+	assert_eq!(boxed.as_str(), "0123");
+	assert_eq!(boxed.as_str().as_ptr(), ptr);
+
+	let owned = boxed.into_owned();
+	assert_eq!(owned.as_str(), "0123");
+	assert_eq!(owned.as_str().as_ptr(), ptr);
+}
+
+#[test]
+fn boxed_conversions() {
+	let owned = DigitsString::new("42".to_owned()).unwrap();
+	let boxed = Box::<DigitsStr>::from(owned);
+	let owned = DigitsString::from(boxed);
+	assert_eq!(owned.as_str(), "42");
+}
@@ -0,0 +1,103 @@
+use std::{
+	cmp::Ordering,
+	collections::HashMap,
+	hash::{BuildHasher, RandomState},
+};
+use str_newtype::StrNewType;
+
+/// Any name, compared case-insensitively.
+#[derive(StrNewType)]
+#[newtype(infallible, case_insensitive)]
+#[repr(transparent)]
+pub struct NameStr(str);
+
+const NAMES: &[&str] = &[
+	"", "a", "A", "ab", "aB", "Ab", "b", "été", "ÉTÉ", "Été", "ete", "straße", "STRASSE", "ǅ", "ǆ",
+	"Ǆ", "Σ", "σ", "ς", "K", "k", "\u{212a}",
+];
+
+#[test]
+fn ordering_agrees_with_equality() {
+	for a in NAMES {
+		let a = NameStr::new(a);
+
+		for b in NAMES {
+			let b = NameStr::new(b);
+
+			let eq = a.case_insensitive() == b.case_insensitive();
+			assert_eq!(eq, a.eq_ignore_case(b), "{a:?} {b:?}");
+			assert_eq!(
+				eq,
+				a.case_insensitive().cmp(&b.case_insensitive()) == Ordering::Equal,
+				"{a:?} {b:?}"
+			);
+			assert_eq!(
+				a.case_insensitive().partial_cmp(&b.case_insensitive()),
+				Some(a.case_insensitive().cmp(&b.case_insensitive()))
+			);
+			assert_eq!(
+				a.case_insensitive().cmp(&b.case_insensitive()),
+				b.case_insensitive().cmp(&a.case_insensitive()).reverse()
+			);
+		}
+	}
+}
+
+#[test]
+fn non_ascii() {
+	let upper = NameStr::new("ÉTÉ");
+	let lower = NameStr::new("été");
+	assert!(upper.eq_ignore_case(lower));
+	assert_eq!(
+		upper.case_insensitive().cmp(&lower.case_insensitive()),
+		Ordering::Equal
+	);
+
+	let ascii = NameStr::new("ete");
+	assert!(!ascii.eq_ignore_case(lower));
+	assert_ne!(
+		ascii.case_insensitive().cmp(&lower.case_insensitive()),
+		Ordering::Equal
+	);
+}
+
+#[test]
+fn equal_values_hash_equally() {
+	let state = RandomState::new();
+
+	for a in NAMES {
+		let a = NameStr::new(a);
+
+		for b in NAMES {
+			let b = NameStr::new(b);
+
+			if a.eq_ignore_case(b) {
+				assert_eq!(
+					state.hash_one(a.case_insensitive()),
+					state.hash_one(b.case_insensitive()),
+					"{a:?} {b:?}"
+				);
+			}
+		}
+	}
+}
+
+#[test]
+fn hash_map_key() {
+	let mut map = HashMap::new();
+	map.insert(NameStr::new("Content-Type").case_insensitive(), 1);
+	map.insert(NameStr::new("ÉTÉ").case_insensitive(), 2);
+
+	assert_eq!(
+		map.get(&NameStr::new("content-type").case_insensitive()),
+		Some(&1)
+	);
+	assert_eq!(map.get(&NameStr::new("été").case_insensitive()), Some(&2));
+	assert_eq!(map.get(&NameStr::new("ete").case_insensitive()), None);
+
+	assert_eq!(
+		map.insert(NameStr::new("CONTENT-TYPE").case_insensitive(), 3),
+		Some(1)
+	);
+	assert_eq!(map.len(), 2);
+}
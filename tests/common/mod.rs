@@ -0,0 +1,94 @@
+//! Validators shared by the integration tests.
+#![allow(dead_code)]
+
+/// Implements the `validate_bytes` and `validate_str` methods expected by
+/// `StrNewType` on the given type, using the given byte validator.
+macro_rules! validate_with {
+	($ty:ident, $validate:path) => {
+		impl $ty {
+			pub const fn validate_bytes(s: &[u8]) -> bool {
+				$validate(s)
+			}
+
+			pub const fn validate_str(s: &str) -> bool {
+				Self::validate_bytes(s.as_bytes())
+			}
+		}
+	};
+}
+
+pub(crate) use validate_with;
+
+/// Single ASCII digit.
+pub const fn digit(s: &[u8]) -> bool {
+	s.len() == 1 && s[0].is_ascii_digit()
+}
+
+/// ASCII digits.
+pub const fn digits(s: &[u8]) -> bool {
+	let mut i = 0;
+	while i < s.len() {
+		if !s[i].is_ascii_digit() {
+			return false;
+		}
+
+		i += 1
+	}
+
+	true
+}
+
+/// Non-empty string of ASCII digits.
+pub const fn non_empty_digits(s: &[u8]) -> bool {
+	!s.is_empty() && digits(s)
+}
+
+/// Lowercase ASCII letters.
+pub const fn lowercase(s: &[u8]) -> bool {
+	let mut i = 0;
+	while i < s.len() {
+		if !s[i].is_ascii_lowercase() {
+			return false;
+		}
+
+		i += 1
+	}
+
+	true
+}
+
+/// Non-empty string without ASCII whitespace.
+pub const fn token(s: &[u8]) -> bool {
+	if s.is_empty() {
+		return false;
+	}
+
+	let mut i = 0;
+	while i < s.len() {
+		if s[i].is_ascii_whitespace() {
+			return false;
+		}
+
+		i += 1
+	}
+
+	true
+}
+
+/// Non-empty string of ASCII letters and spaces.
+pub const fn words(s: &[u8]) -> bool {
+	if s.is_empty() {
+		return false;
+	}
+
+	let mut i = 0;
+	while i < s.len() {
+		if !s[i].is_ascii_alphabetic() && s[i] != b' ' {
+			return false;
+		}
+
+		i += 1
+	}
+
+	true
+}
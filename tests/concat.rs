@@ -0,0 +1,34 @@
+mod common;
+
+use str_newtype::StrNewType;
+
+/// ASCII digits, closed under concatenation.
+#[derive(StrNewType)]
+#[newtype(concat, owned(DigitsString))]
+#[repr(transparent)]
+pub struct DigitsStr(str);
+
+common::validate_with!(DigitsStr, common::digits);
+
+#[test]
+fn add() {
+	let a = DigitsString::new("12".to_owned()).unwrap();
+	let b = DigitsStr::new("34").unwrap();
+	let c = a + b;
+	assert_eq!(c.as_str(), "1234");
+	assert!(DigitsStr::validate_str(c.as_str()));
+}
+
+#[test]
+fn add_assign() {
+	let mut a = DigitsString::new(String::with_capacity(16)).unwrap();
+	let ptr = a.as_str().as_ptr();
+	a += DigitsStr::new("0").unwrap();
+	a += DigitsStr::new("").unwrap();
+	a += DigitsStr::new("99").unwrap();
+	assert_eq!(a.as_str(), "099");
+	assert!(DigitsStr::validate_str(a.as_str()));
+
+	// The buffer of the left-hand side is reused.
+	assert_eq!(a.as_str().as_ptr(), ptr);
+}
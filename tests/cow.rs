@@ -0,0 +1,25 @@
+use std::borrow::Cow;
+use str_newtype::StrNewType;
+
+/// Any string.
+#[derive(StrNewType)]
+#[newtype(infallible, owned(AnyString))]
+#[repr(transparent)]
+pub struct AnyStr(str);
+
+#[test]
+fn borrowed_into_owned() {
+	let cow: Cow<AnyStr> = Cow::Borrowed(AnyStr::new("foo"));
+	let owned: AnyString = cow.into_owned();
+	assert_eq!(owned.as_str(), "foo");
+}
+
+#[test]
+fn owned_into_owned() {
+	let owned = AnyString::new("bar");
+	let ptr = owned.as_str().as_ptr();
+	let cow: Cow<AnyStr> = Cow::Owned(owned);
+	let owned: AnyString = cow.into_owned();
+	assert_eq!(owned.as_str(), "bar");
+	assert_eq!(owned.as_str().as_ptr(), ptr);
+}
@@ -0,0 +1,66 @@
+mod common;
+
+use diesel::{prelude::*, result::Error};
+use str_newtype::StrNewType;
+
+/// ASCII digits.
+#[derive(StrNewType)]
+#[newtype(diesel, owned(DigitsString))]
+#[repr(transparent)]
+pub struct DigitsStr(str);
+
+common::validate_with!(DigitsStr, common::digits);
+
+diesel::table! {
+	codes (id) {
+		id -> Integer,
+		code -> Text,
+	}
+}
+
+#[derive(Queryable, Insertable)]
+#[diesel(table_name = codes)]
+struct Code {
+	id: i32,
+	code: DigitsString,
+}
+
+fn connect() -> SqliteConnection {
+	let mut conn = SqliteConnection::establish(":memory:").unwrap();
+	diesel::sql_query("CREATE TABLE codes (id INTEGER PRIMARY KEY, code TEXT NOT NULL)")
+		.execute(&mut conn)
+		.unwrap();
+	conn
+}
+
+#[test]
+fn round_trip() {
+	let mut conn = connect();
+
+	diesel::insert_into(codes::table)
+		.values(&Code {
+			id: 1,
+			code: DigitsString::new("0123".to_owned()).unwrap(),
+		})
+		.execute(&mut conn)
+		.unwrap();
+
+	let code: Code = codes::table
+		.filter(codes::code.eq(DigitsStr::new("0123").unwrap()))
+		.first(&mut conn)
+		.unwrap();
+	assert_eq!(code.id, 1);
+	assert_eq!(code.code.as_str(), "0123");
+}
+
+#[test]
+fn invalid_value_is_rejected() {
+	let mut conn = connect();
+
+	diesel::sql_query("INSERT INTO codes (id, code) VALUES (1, '12a')")
+		.execute(&mut conn)
+		.unwrap();
+
+	let result = codes::table.first::<Code>(&mut conn);
+	assert!(matches!(result, Err(Error::DeserializationError(_))));
+}
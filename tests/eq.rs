@@ -0,0 +1,65 @@
+use std::cmp::Ordering;
+use str_newtype::StrNewType;
+
+/// ASCII digits.
+///
+/// Validating an input containing `!` panics, to check that comparisons do
+/// not validate the other operand.
+#[derive(StrNewType, PartialEq, Eq, PartialOrd, Ord)]
+#[newtype(
+	eq(str),
+	ord(str, String),
+	owned(DigitsString, derive(PartialEq, PartialOrd))
+)]
+#[repr(transparent)]
+pub struct DigitsStr(str);
+
+impl DigitsStr {
+	pub const fn validate_bytes(s: &[u8]) -> bool {
+		let mut i = 0;
+		while i < s.len() {
+			if s[i] == b'!' {
+				panic!("validation invoked")
+			}
+
+			if !s[i].is_ascii_digit() {
+				return false;
+			}
+
+			i += 1
+		}
+
+		true
+	}
+
+	pub const fn validate_str(s: &str) -> bool {
+		Self::validate_bytes(s.as_bytes())
+	}
+}
+
+#[test]
+fn eq_invalid_does_not_validate() {
+	let d = DigitsStr::new("12").unwrap();
+	let invalid = String::from("bar!");
+	assert!(d != "bar!");
+	assert!(*d != *"bar!");
+	assert!(d != invalid);
+	assert!(d == "12");
+
+	let owned = d.to_owned();
+	assert!(owned != *"bar!");
+	assert!(owned != invalid);
+}
+
+#[test]
+fn ord_invalid_does_not_validate() {
+	let d = DigitsStr::new("12").unwrap();
+	assert_eq!(d.partial_cmp("bar!"), Some(Ordering::Less));
+	assert_eq!(d.partial_cmp("!"), Some(Ordering::Greater));
+	assert_eq!(d.partial_cmp(&"bar!".to_owned()), Some(Ordering::Less));
+	assert_eq!(d.partial_cmp("12"), Some(Ordering::Equal));
+	assert!(*d < *"bar!");
+
+	let owned = d.to_owned();
+	assert_eq!(owned.partial_cmp("bar!"), Some(Ordering::Less));
+}
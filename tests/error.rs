@@ -0,0 +1,94 @@
+mod common;
+
+use std::{
+	collections::HashSet,
+	hash::{BuildHasher, RandomState},
+};
+use str_newtype::StrNewType;
+
+/// Single ASCII digit.
+#[derive(StrNewType)]
+#[newtype(owned(DigitString))]
+#[repr(transparent)]
+pub struct DigitStr(str);
+
+common::validate_with!(DigitStr, common::digit);
+
+/// ASCII digits, reporting the offset of the first invalid byte.
+#[derive(StrNewType)]
+#[newtype(validate_result(usize), owned(DigitsString))]
+#[repr(transparent)]
+pub struct DigitsStr(str);
+
+impl DigitsStr {
+	pub const fn validate_bytes(s: &[u8]) -> Result<(), usize> {
+		let mut i = 0;
+		while i < s.len() {
+			if !s[i].is_ascii_digit() {
+				return Err(i);
+			}
+
+			i += 1
+		}
+
+		Ok(())
+	}
+
+	pub const fn validate_str(s: &str) -> Result<(), usize> {
+		Self::validate_bytes(s.as_bytes())
+	}
+}
+
+#[test]
+fn eq() {
+	assert_eq!(
+		DigitStr::new("bad").unwrap_err(),
+		DigitStr::new("bad").unwrap_err()
+	);
+	assert_ne!(
+		DigitStr::new("bad").unwrap_err(),
+		DigitStr::new("worse").unwrap_err()
+	);
+	assert_eq!(
+		DigitString::new("bad".to_owned()).unwrap_err(),
+		InvalidDigitStr("bad".to_owned())
+	);
+}
+
+#[test]
+fn clone() {
+	let e = DigitString::new("bad".to_owned()).unwrap_err();
+	let cloned = e.clone();
+	assert_eq!(cloned, e);
+	assert_eq!(cloned.into_inner(), "bad");
+}
+
+#[test]
+fn hash() {
+	let state = RandomState::new();
+	let a = DigitStr::from_str("bad").unwrap_err();
+	let b = DigitStr::from_str("bad").unwrap_err();
+	assert_eq!(state.hash_one(a), state.hash_one(b));
+
+	let set: HashSet<_> = ["a", "b", "a"]
+		.into_iter()
+		.map(|s| DigitStr::from_str(s).unwrap_err())
+		.collect();
+	assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn reason_is_compared() {
+	assert_eq!(
+		DigitsStr::new("1a").unwrap_err(),
+		DigitsStr::new("1a").unwrap_err()
+	);
+	assert_ne!(
+		DigitsStr::new("1a").unwrap_err(),
+		DigitsStr::new("a1").unwrap_err()
+	);
+
+	let e = "12a4".parse::<DigitsString>().unwrap_err();
+	assert_eq!(e.clone(), e);
+	assert_eq!(e.1, Some(2));
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use std::{error::Error, str::FromStr};
+use str_newtype::StrNewType;
+
+/// Single ASCII digit.
+#[derive(StrNewType)]
+#[newtype(owned(DigitString))]
+#[repr(transparent)]
+pub struct DigitStr(str);
+
+common::validate_with!(DigitStr, common::digit);
+
+/// Parses a value the way frameworks requiring boxable `FromStr` errors do.
+fn parse<T>(s: &str) -> Result<T, Box<dyn Error + Send + Sync + 'static>>
+where
+	T: FromStr,
+	T::Err: Into<Box<dyn Error + Send + Sync + 'static>>,
+{
+	s.parse().map_err(Into::into)
+}
+
+#[test]
+fn error_boxes_into_dyn_error() {
+	assert_eq!(parse::<DigitString>("1").unwrap().as_str(), "1");
+
+	let e = parse::<DigitString>("a").unwrap_err();
+	assert_eq!(e.to_string(), "invalid digitstr: a");
+	assert!(e.downcast_ref::<InvalidDigitStr>().is_some());
+}
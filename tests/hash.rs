@@ -0,0 +1,40 @@
+mod common;
+
+use std::{
+	collections::HashSet,
+	hash::{BuildHasher, RandomState},
+};
+use str_newtype::StrNewType;
+
+/// Lowercase ASCII word.
+#[derive(StrNewType, PartialEq, Eq)]
+#[newtype(hash, owned(WordString, derive(PartialEq, Eq, Hash)))]
+#[repr(transparent)]
+pub struct WordStr(str);
+
+common::validate_with!(WordStr, common::lowercase);
+
+#[test]
+fn borrowed_and_owned_hash_the_same() {
+	let state = RandomState::new();
+	let owned = WordString::new("foo".to_owned()).unwrap();
+	let borrowed = WordStr::new("foo").unwrap();
+	assert_eq!(state.hash_one(&owned), state.hash_one(borrowed));
+	assert_eq!(state.hash_one(borrowed), state.hash_one("foo"));
+}
+
+#[test]
+fn set_lookup_through_borrow() {
+	let mut set: HashSet<WordString> = HashSet::new();
+	set.insert(WordStr::new("foo").unwrap().to_owned());
+	set.insert(WordString::new("bar".to_owned()).unwrap());
+	assert!(!set.insert(WordString::new("foo".to_owned()).unwrap()));
+
+	assert!(set.contains(WordStr::new("foo").unwrap()));
+	assert!(set.contains(WordStr::new("bar").unwrap()));
+	assert!(!set.contains(WordStr::new("baz").unwrap()));
+
+	let borrowed: HashSet<&WordStr> = set.iter().map(WordString::as_word_str).collect();
+	assert!(borrowed.contains(WordStr::new("foo").unwrap()));
+	assert_eq!(borrowed.len(), 2);
+}
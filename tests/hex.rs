@@ -0,0 +1,33 @@
+use str_newtype::StrNewType;
+
+/// Any bytes.
+#[derive(StrNewType)]
+#[newtype(infallible, owned(TokenBuf))]
+#[repr(transparent)]
+pub struct Token([u8]);
+
+#[test]
+fn hex() {
+	let t = Token::new(&[0x0a, 0xff]);
+	assert_eq!(format!("{t:x}"), "0aff");
+	assert_eq!(format!("{t:X}"), "0AFF");
+	assert_eq!(format!("{t:#x}"), "0x0aff");
+	assert_eq!(format!("{:x}", t.to_owned()), "0aff");
+	assert_eq!(format!("{:x}", Token::new(&[])), "");
+}
+
+#[test]
+fn hex_padding() {
+	let t = Token::new(&[0x0a, 0xff]);
+	assert_eq!(format!("{t:08x}"), "00000aff");
+	assert_eq!(format!("{t:#08x}"), "0x000aff");
+	assert_eq!(format!("{t:8X}"), "    0AFF");
+	assert_eq!(format!("{t:<8x}|"), "0aff    |");
+	assert_eq!(format!("{t:*^9x}"), "**0aff***");
+	assert_eq!(format!("{t:02x}"), "0aff");
+	assert_eq!(format!("{:>8x}", t.to_owned()), "    0aff");
+
+	// Same as integers and strings.
+	assert_eq!(format!("{t:#08x}"), format!("{:#08x}", 0x0affu16));
+	assert_eq!(format!("{t:*^9x}"), format!("{:*^9}", "0aff"));
+}
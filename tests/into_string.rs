@@ -0,0 +1,41 @@
+mod common;
+
+use str_newtype::StrNewType;
+
+/// Any string.
+#[derive(StrNewType)]
+#[newtype(infallible, owned(AnyString))]
+#[repr(transparent)]
+pub struct AnyStr(str);
+
+/// Single ASCII digit.
+#[derive(StrNewType)]
+#[newtype(owned(DigitString))]
+#[repr(transparent)]
+pub struct DigitStr(str);
+
+common::validate_with!(DigitStr, common::digit);
+
+#[test]
+fn string_from_owned_does_not_reallocate() {
+	let owned = AnyString::new("hello");
+	let ptr = owned.as_str().as_ptr();
+	let s = String::from(owned);
+	assert_eq!(s, "hello");
+	assert_eq!(s.as_ptr(), ptr);
+
+	let owned = DigitString::new("1".to_owned()).unwrap();
+	let ptr = owned.as_str().as_ptr();
+	let s = String::from(owned);
+	assert_eq!(s, "1");
+	assert_eq!(s.as_ptr(), ptr);
+}
+
+#[test]
+fn into_string_does_not_reallocate() {
+	let input = "world".to_owned();
+	let ptr = input.as_ptr();
+	let s = AnyString::new(input).into_string();
+	assert_eq!(s, "world");
+	assert_eq!(s.as_ptr(), ptr);
+}
@@ -0,0 +1,30 @@
+mod common;
+
+use str_newtype::StrNewType;
+
+/// Single ASCII digit, without `Deref<Target = str>`.
+#[derive(StrNewType, PartialEq, Eq, PartialOrd, Ord)]
+#[newtype(no_deref, eq(str), ord(str), owned(DigitString, derive(PartialEq)))]
+#[repr(transparent)]
+pub struct DigitStr(str);
+
+common::validate_with!(DigitStr, common::digit);
+
+#[test]
+fn eq_str() {
+	let d = DigitStr::new("1").unwrap();
+	assert!(*d == *"1");
+	assert!(*"1" == *d);
+	assert!(d == "1");
+	assert!(d != "2");
+	assert!(d != "a");
+	assert!(DigitString::new("1".to_owned()).unwrap() == *"1");
+}
+
+#[test]
+fn ord_str() {
+	let d = DigitStr::new("5").unwrap();
+	assert!(*d < *"6");
+	assert!(*d > *"1");
+	assert!(*d < *"a");
+}
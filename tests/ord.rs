@@ -0,0 +1,28 @@
+use std::cmp::Reverse;
+use str_newtype::StrNewType;
+
+/// Any string.
+#[derive(StrNewType, PartialEq, Eq, PartialOrd, Ord)]
+#[newtype(infallible, owned(AnyString, derive(PartialEq, Eq, PartialOrd, Ord)))]
+#[repr(transparent)]
+pub struct AnyStr(str);
+
+fn strings(list: &[&str]) -> Vec<AnyString> {
+	list.iter().map(|s| AnyString::new(*s)).collect()
+}
+
+#[test]
+fn sort_owned_descending() {
+	let mut vec = strings(&["b", "c", "a"]);
+	vec.sort_by_key(|f| Reverse(f.clone()));
+	assert_eq!(vec, strings(&["c", "b", "a"]));
+}
+
+#[test]
+fn sort_borrowed_descending() {
+	let owned = strings(&["b", "c", "a"]);
+	let mut vec: Vec<&AnyStr> = owned.iter().map(|s| s.as_any_str()).collect();
+	vec.sort_by_key(|f| Reverse(*f));
+	let sorted: Vec<&str> = vec.into_iter().map(AnyStr::as_str).collect();
+	assert_eq!(sorted, ["c", "b", "a"]);
+}
@@ -0,0 +1,46 @@
+use str_newtype::StrNewType;
+
+/// Any string, with an owned type named `IriString`.
+#[derive(StrNewType)]
+#[newtype(infallible, owned)]
+#[repr(transparent)]
+pub struct IriStr(str);
+
+/// Any string, with an owned type named `UriBuf`.
+#[derive(StrNewType)]
+#[newtype(infallible, owned)]
+#[repr(transparent)]
+pub struct Uri(str);
+
+/// Any string, with an owned type named `UrnBuf`.
+#[derive(StrNewType)]
+#[newtype(infallible, owned(suffix = "Buf"))]
+#[repr(transparent)]
+pub struct UrnStr(str);
+
+/// Any string, with an owned type named `PathOwned`.
+#[derive(StrNewType)]
+#[newtype(infallible, owned(suffix = "Owned"))]
+#[repr(transparent)]
+pub struct Path(str);
+
+#[test]
+fn str_suffix_is_replaced() {
+	let owned: IriString = IriStr::new("foo").to_owned();
+	assert_eq!(owned.as_str(), "foo");
+}
+
+#[test]
+fn buf_is_appended() {
+	let owned: UriBuf = Uri::new("foo").to_owned();
+	assert_eq!(owned.as_str(), "foo");
+}
+
+#[test]
+fn custom_suffix() {
+	let owned: UrnBuf = UrnStr::new("foo").to_owned();
+	assert_eq!(owned.as_str(), "foo");
+
+	let owned: PathOwned = Path::new("foo").to_owned();
+	assert_eq!(owned.as_str(), "foo");
+}
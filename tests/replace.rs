@@ -0,0 +1,41 @@
+mod common;
+
+use str_newtype::StrNewType;
+
+/// Non-empty string of ASCII digits.
+#[derive(StrNewType)]
+#[newtype(owned(DigitsString))]
+#[repr(transparent)]
+pub struct DigitsStr(str);
+
+common::validate_with!(DigitsStr, common::non_empty_digits);
+
+#[test]
+fn accepted_replace() {
+	let mut s = DigitsString::new("1212".to_owned()).unwrap();
+	s.try_replace("2", "34").unwrap();
+	assert_eq!(s.as_str(), "134134");
+}
+
+#[test]
+fn rejected_replace_rolls_back() {
+	let mut s = DigitsString::new("1212".to_owned()).unwrap();
+	let e = s.try_replace("2", "x").unwrap_err();
+	assert_eq!(s.as_str(), "1212");
+	assert_eq!(e.0, "1x1x");
+
+	// Removing every digit gives an empty (invalid) string.
+	let e = s.try_replace("12", "").unwrap_err();
+	assert_eq!(s.as_str(), "1212");
+	assert_eq!(e.0, "");
+}
+
+#[test]
+fn str_replace_is_not_shadowed() {
+	fn replace(s: &DigitsString) -> String {
+		s.replace("1", "a")
+	}
+
+	let s = DigitsString::new("1212".to_owned()).unwrap();
+	assert_eq!(replace(&s), "a2a2");
+}
@@ -0,0 +1,27 @@
+mod common;
+
+use str_newtype::StrNewType;
+
+/// Lowercase ASCII word.
+#[derive(StrNewType)]
+#[repr(transparent)]
+pub struct WordStr(str);
+
+common::validate_with!(WordStr, common::lowercase);
+
+#[test]
+fn from_str_as_str() {
+	let input = "hello";
+	let word = WordStr::from_str(input).unwrap();
+	assert_eq!(word.as_str(), input);
+	assert_eq!(word.as_str().as_ptr(), input.as_ptr());
+	assert_eq!(word.len(), input.len());
+}
+
+#[test]
+fn from_bytes_as_bytes() {
+	let input = b"hello";
+	let word = WordStr::from_bytes(input).unwrap();
+	assert_eq!(word.as_bytes(), input);
+	assert_eq!(word.as_bytes().as_ptr(), input.as_ptr());
+}
@@ -0,0 +1,59 @@
+mod common;
+
+use serde_json::json;
+use str_newtype::StrNewType;
+
+/// ASCII digits.
+#[derive(StrNewType)]
+#[newtype(schemars, owned(DigitsString))]
+#[repr(transparent)]
+pub struct DigitsStr(str);
+
+common::validate_with!(DigitsStr, common::digits);
+
+/// Four ASCII digits.
+#[derive(StrNewType)]
+#[newtype(schemars(format = "pin", pattern = "^[0-9]{4}$"), owned(PinString))]
+#[repr(transparent)]
+pub struct PinStr(str);
+
+impl PinStr {
+	pub const fn validate_bytes(s: &[u8]) -> bool {
+		s.len() == 4 && common::digits(s)
+	}
+
+	pub const fn validate_str(s: &str) -> bool {
+		Self::validate_bytes(s.as_bytes())
+	}
+}
+
+#[test]
+fn string_schema() {
+	let schema = serde_json::to_value(schemars::schema_for!(DigitsStr)).unwrap();
+	assert_eq!(schema["type"], "string");
+	assert_eq!(schema["title"], "DigitsStr");
+	assert!(schema.get("format").is_none());
+	assert!(schema.get("pattern").is_none());
+}
+
+#[test]
+fn format_and_pattern() {
+	let schema = serde_json::to_value(schemars::schema_for!(PinStr)).unwrap();
+	assert_eq!(schema["type"], "string");
+	assert_eq!(schema["format"], "pin");
+	assert_eq!(schema["pattern"], "^[0-9]{4}$");
+}
+
+#[test]
+fn owned_type_shares_the_schema() {
+	let schema = serde_json::to_value(schemars::schema_for!(Vec<PinString>)).unwrap();
+	assert_eq!(schema["items"]["$ref"], "#/$defs/PinStr");
+	assert_eq!(
+		schema["$defs"]["PinStr"],
+		json!({
+			"type": "string",
+			"format": "pin",
+			"pattern": "^[0-9]{4}$"
+		})
+	);
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use serde::Deserialize;
+use serde_test::{
+	Compact, Configure, Token, assert_de_tokens, assert_de_tokens_error, assert_ser_tokens,
+};
+use std::{borrow::Cow, sync::Arc};
+use str_newtype::StrNewType;
+
+/// ASCII digits.
+#[derive(StrNewType)]
+#[newtype(serde, serde_json, rc, list(DigitsList), owned(DigitsString))]
+#[repr(transparent)]
+pub struct DigitsStr(str);
+
+common::validate_with!(DigitsStr, common::digits);
+
+/// ASCII digits, stored as bytes in binary formats.
+#[derive(StrNewType, PartialEq)]
+#[newtype(serde(human_readable), owned(CompactDigitsString, derive(PartialEq)))]
+#[repr(transparent)]
+pub struct CompactDigitsStr(str);
+
+common::validate_with!(CompactDigitsStr, common::digits);
+
+#[derive(Deserialize)]
+struct CowCode<'a> {
+	#[serde(borrow, deserialize_with = "DigitsStr::deserialize_cow")]
+	code: Cow<'a, DigitsStr>,
+}
+
+#[derive(Deserialize)]
+struct ArcCode {
+	#[serde(deserialize_with = "DigitsStr::deserialize_arc")]
+	code: Arc<DigitsStr>,
+}
+
+#[test]
+fn round_trip() {
+	let borrowed: &DigitsStr = serde_json::from_str("\"0123\"").unwrap();
+	assert_eq!(borrowed.as_str(), "0123");
+	assert_eq!(serde_json::to_string(borrowed).unwrap(), "\"0123\"");
+
+	let owned: DigitsString = serde_json::from_str("\"0123\"").unwrap();
+	assert_eq!(owned.as_str(), "0123");
+	assert_eq!(serde_json::to_string(&owned).unwrap(), "\"0123\"");
+
+	assert!(serde_json::from_str::<&DigitsStr>("\"12a\"").is_err());
+	assert!(serde_json::from_str::<DigitsString>("\"12a\"").is_err());
+}
+
+#[test]
+fn cow_borrows_plain_input() {
+	let input = r#"{"code":"0123"}"#;
+	let value: CowCode = serde_json::from_str(input).unwrap();
+	assert!(matches!(value.code, Cow::Borrowed(_)));
+	assert_eq!(value.code.as_str(), "0123");
+}
+
+#[test]
+fn cow_owns_escaped_input() {
+	let input = r#"{"code":"012\u0033"}"#;
+	let value: CowCode = serde_json::from_str(input).unwrap();
+	assert!(matches!(value.code, Cow::Owned(_)));
+	assert_eq!(value.code.as_str(), "0123");
+
+	assert!(serde_json::from_str::<CowCode>(r#"{"code":"12a"}"#).is_err());
+	assert!(serde_json::from_str::<CowCode>(r#"{"code":"12\u0061"}"#).is_err());
+}
+
+#[test]
+fn boxed_and_shared() {
+	let boxed: Box<DigitsStr> = serde_json::from_str("\"0123\"").unwrap();
+	assert_eq!(serde_json::to_string(&boxed).unwrap(), "\"0123\"");
+	assert!(serde_json::from_str::<Box<DigitsStr>>("\"12a\"").is_err());
+
+	let arc: Arc<DigitsStr> = serde_json::from_str("\"0123\"").unwrap();
+	assert_eq!(serde_json::to_string(&arc).unwrap(), "\"0123\"");
+
+	let value: ArcCode = serde_json::from_str(r#"{"code":"0123"}"#).unwrap();
+	assert_eq!(value.code.as_str(), "0123");
+	assert!(serde_json::from_str::<ArcCode>(r#"{"code":"12a"}"#).is_err());
+}
+
+#[test]
+fn human_readable() {
+	let value = CompactDigitsString::new("0123".to_owned()).unwrap();
+
+	assert_ser_tokens(&value.clone().readable(), &[Token::Str("0123")]);
+	assert_ser_tokens(&value.clone().compact(), &[Token::Bytes(b"0123")]);
+
+	assert_de_tokens(&value.clone().readable(), &[Token::Str("0123")]);
+	assert_de_tokens(&value.clone().compact(), &[Token::Bytes(b"0123")]);
+	assert_de_tokens(&value.compact(), &[Token::ByteBuf(b"0123")]);
+
+	assert_de_tokens_error::<Compact<CompactDigitsString>>(
+		&[Token::Bytes(b"12a")],
+		"invalid compactdigitsstr: 12a",
+	);
+}
+
+#[test]
+fn list() {
+	let list: DigitsList = serde_json::from_str("\"1,22,333\"").unwrap();
+	assert_eq!(list.len(), 3);
+	assert_eq!(list[1].as_str(), "22");
+	assert_eq!(serde_json::to_string(&list).unwrap(), "\"1,22,333\"");
+
+	assert!(serde_json::from_str::<DigitsList>("\"1,2a\"").is_err());
+}
+
+#[test]
+fn json_value() {
+	let value = serde_json::json!("0123");
+	let borrowed = <&DigitsStr>::try_from(&value).unwrap();
+	assert_eq!(borrowed.as_str(), "0123");
+	assert!(std::ptr::eq(borrowed.as_str(), value.as_str().unwrap()));
+
+	let invalid = serde_json::json!("12a");
+	assert_eq!(<&DigitsStr>::try_from(&invalid).unwrap_err().0, &invalid);
+
+	let number = serde_json::json!(12);
+	assert_eq!(<&DigitsStr>::try_from(&number).unwrap_err().0, &number);
+}
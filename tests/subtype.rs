@@ -0,0 +1,111 @@
+use str_newtype::StrNewType;
+
+/// Non-empty ASCII string.
+#[derive(StrNewType)]
+#[newtype(owned(IriString))]
+#[repr(transparent)]
+pub struct IriStr(str);
+
+impl IriStr {
+	pub const fn validate_bytes(s: &[u8]) -> bool {
+		!s.is_empty() && s.is_ascii()
+	}
+
+	pub const fn validate_str(s: &str) -> bool {
+		Self::validate_bytes(s.as_bytes())
+	}
+
+	pub fn scheme(&self) -> Option<&str> {
+		self.as_str().split_once(':').map(|(scheme, _)| scheme)
+	}
+}
+
+/// Any string.
+#[derive(StrNewType)]
+#[newtype(infallible, owned(AnyString))]
+#[repr(transparent)]
+pub struct AnyStr(str);
+
+/// Non-empty ASCII string with a scheme.
+#[derive(StrNewType)]
+#[newtype(
+	deref(IriStr),
+	subtype_of(IriStr, IriString),
+	subtype_of(AnyStr, AnyString),
+	owned(AbsoluteIriString)
+)]
+#[repr(transparent)]
+pub struct AbsoluteIriStr(str);
+
+impl AbsoluteIriStr {
+	pub const fn validate_bytes(s: &[u8]) -> bool {
+		if !IriStr::validate_bytes(s) {
+			return false;
+		}
+
+		let mut i = 0;
+		while i < s.len() {
+			if s[i] == b':' {
+				return i > 0;
+			}
+
+			i += 1
+		}
+
+		false
+	}
+
+	pub const fn validate_str(s: &str) -> bool {
+		Self::validate_bytes(s.as_bytes())
+	}
+}
+
+#[test]
+fn borrowed_conversion_is_a_cast() {
+	let abs = AbsoluteIriStr::new("https:example").unwrap();
+	let iri: &IriStr = abs.into();
+	assert_eq!(iri.as_str(), "https:example");
+	assert_eq!(iri.as_str().as_ptr(), abs.as_str().as_ptr());
+
+	let iri: &IriStr = abs.as_ref();
+	assert_eq!(iri.as_str().as_ptr(), abs.as_str().as_ptr());
+}
+
+#[test]
+fn owned_conversion_reuses_the_buffer() {
+	let abs = AbsoluteIriString::new("https:example".to_owned()).unwrap();
+	let ptr = abs.as_str().as_ptr();
+
+	let iri: &IriStr = abs.as_ref();
+	assert_eq!(iri.as_str().as_ptr(), ptr);
+
+	let iri = IriString::from(abs);
+	assert_eq!(iri.as_str(), "https:example");
+	assert_eq!(iri.as_str().as_ptr(), ptr);
+}
+
+#[test]
+fn deref_is_transitive() {
+	let abs = AbsoluteIriStr::new("urn:isbn").unwrap();
+
+	// `IriStr` method, through `Deref<Target = IriStr>`.
+	assert_eq!(abs.scheme(), Some("urn"));
+
+	// `str` method, through `IriStr: Deref<Target = str>`.
+	assert_eq!(abs.len(), 8);
+	assert!(abs.starts_with("urn:"));
+
+	let owned = AbsoluteIriString::new("urn:isbn".to_owned()).unwrap();
+	assert_eq!(owned.scheme(), Some("urn"));
+	assert_eq!(owned.len(), 8);
+}
+
+#[test]
+fn infallible_owned_conversion_reuses_the_buffer() {
+	let abs = AbsoluteIriString::new("https:example".to_owned()).unwrap();
+	let ptr = abs.as_str().as_ptr();
+
+	let any = AnyString::from(abs);
+	assert_eq!(any.as_str(), "https:example");
+	assert_eq!(any.as_str().as_ptr(), ptr);
+}
@@ -0,0 +1,63 @@
+mod common;
+
+use str_newtype::StrNewType;
+
+/// Any string, trimmed on construction.
+#[derive(StrNewType)]
+#[newtype(infallible, trim, owned(TrimmedString))]
+#[repr(transparent)]
+pub struct TrimmedStr(str);
+
+/// Non-empty string of ASCII letters and spaces, trimmed on construction.
+#[derive(StrNewType)]
+#[newtype(trim, owned(WordsString))]
+#[repr(transparent)]
+pub struct WordsStr(str);
+
+common::validate_with!(WordsStr, common::words);
+
+#[test]
+fn constructors_trim() {
+	assert_eq!(TrimmedString::new("  a b  ").as_str(), "a b");
+	assert_eq!(" x ".parse::<TrimmedString>().unwrap().as_str(), "x");
+	assert_eq!(
+		WordsString::new(" a b ".to_owned()).unwrap().as_str(),
+		"a b"
+	);
+	assert_eq!(" a ".parse::<WordsString>().unwrap().as_str(), "a");
+}
+
+#[test]
+fn infallible_mutations_do_not_trim() {
+	let mut s = TrimmedString::new("x");
+	s.push_str(" y ");
+	assert_eq!(s.as_str(), "x y ");
+
+	s.push(' ');
+	assert_eq!(s.as_str(), "x y  ");
+
+	s.extend([" z"]);
+	assert_eq!(s.as_str(), "x y   z");
+
+	s.mutate(|buffer| buffer.insert(0, ' '));
+	assert_eq!(s.as_str(), " x y   z");
+}
+
+#[test]
+fn fallible_mutations_do_not_trim() {
+	let mut s = WordsString::new("a".to_owned()).unwrap();
+	s.try_push_str(" b ").unwrap();
+	assert_eq!(s.as_str(), "a b ");
+
+	s.try_push(' ').unwrap();
+	assert_eq!(s.as_str(), "a b  ");
+
+	s.append_all([" c"]).unwrap();
+	assert_eq!(s.as_str(), "a b   c");
+
+	s.try_mutate(|buffer| buffer.insert(0, ' ')).unwrap();
+	assert_eq!(s.as_str(), " a b   c");
+
+	s.try_replace("c", "d ").unwrap();
+	assert_eq!(s.as_str(), " a b   d ");
+}
@@ -0,0 +1,20 @@
+use str_newtype::StrNewType;
+
+#[derive(StrNewType)]
+#[newtype(empty)]
+#[repr(transparent)]
+pub struct NonEmptyStr(str);
+
+impl NonEmptyStr {
+	pub const fn validate_bytes(s: &[u8]) -> bool {
+		!s.is_empty()
+	}
+
+	pub const fn validate_str(s: &str) -> bool {
+		!s.is_empty()
+	}
+}
+
+fn main() {
+	let _ = NonEmptyStr::EMPTY;
+}
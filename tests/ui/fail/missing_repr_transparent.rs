@@ -0,0 +1,7 @@
+use str_newtype::StrNewType;
+
+#[derive(StrNewType)]
+#[newtype(infallible)]
+pub struct FooStr(str);
+
+fn main() {}
@@ -0,0 +1,8 @@
+use str_newtype::StrNewType;
+
+#[derive(StrNewType)]
+#[newtype(infallible, owned("FooString"))]
+#[repr(transparent)]
+pub struct FooStr(str);
+
+fn main() {}
@@ -0,0 +1,34 @@
+// The owned type given to `subtype_of` must be the owned type of an `str`
+// new-type, since the buffer is moved into it.
+use str_newtype::StrNewType;
+
+#[derive(StrNewType)]
+#[newtype(infallible, owned(WideString))]
+#[repr(transparent)]
+pub struct WideStr(str);
+
+#[derive(StrNewType)]
+#[newtype(owned(WideBuf))]
+#[repr(transparent)]
+pub struct WideBytes([u8]);
+
+impl WideBytes {
+	pub const fn validate_bytes(_: &[u8]) -> bool {
+		true
+	}
+}
+
+/// Same size as `String`.
+pub struct ThreeWords(usize, usize, usize);
+
+#[derive(StrNewType)]
+#[newtype(infallible, subtype_of(WideStr, WideBuf), owned(NarrowString))]
+#[repr(transparent)]
+pub struct NarrowStr(str);
+
+#[derive(StrNewType)]
+#[newtype(infallible, subtype_of(WideStr, ThreeWords), owned(OtherString))]
+#[repr(transparent)]
+pub struct OtherStr(str);
+
+fn main() {}
@@ -0,0 +1,12 @@
+// `owned(...)` without a type name used to panic. The owned type is now named
+// after the borrowed one (`FooStr` gives `FooString`).
+use str_newtype::StrNewType;
+
+#[derive(StrNewType)]
+#[newtype(infallible, owned(derive(Hash)))]
+#[repr(transparent)]
+pub struct FooStr(str);
+
+fn main() {
+	let _: FooString = FooStr::new("foo").to_owned();
+}